@@ -0,0 +1,34 @@
+//! Times repeated parsing of short strings with one shared [`rfdate::DateParser`].
+//! Run with `cargo bench`.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use rfdate::{DateOrder, DateParser, ParseOptions};
+
+const INPUTS: [&str; 4] = [
+    "order shipped 2023-10-05",
+    "due 05/10/2023 at noon",
+    "no date in this one",
+    "from 2021-11-21 to 2022.01.31",
+];
+
+fn main() {
+    let parser = DateParser::new(ParseOptions {
+        order: Some(DateOrder::Dmy),
+        ..Default::default()
+    });
+    let iterations = 100_000;
+    let start = Instant::now();
+    for _ in 0..iterations {
+        for input in INPUTS {
+            black_box(parser.find_dates(black_box(input)));
+        }
+    }
+    let elapsed = start.elapsed();
+    let calls = iterations * INPUTS.len() as u32;
+    println!(
+        "{calls} calls in {elapsed:?}, {:?} per call",
+        elapsed / calls
+    );
+}
@@ -1,7 +1,11 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fmt::Display;
 use std::num::ParseIntError;
+use std::ops::Range;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-#[derive(Debug, PartialEq, Default)]
+#[derive(Debug, PartialEq, Eq, Hash, Default, Clone, Copy)]
 pub struct Date {
     year: Option<u16>,
     month: Option<u16>,
@@ -14,6 +18,50 @@ impl Date {
             ..Default::default()
         }
     }
+    /// Builds a date from its components, rejecting a component without the coarser one it
+    /// belongs to, such as a day without a month, with `OrphanedComponent`, and an impossible
+    /// date as [`Date::validate`] does.
+    pub fn from_parts(
+        year: Option<u16>,
+        month: Option<u16>,
+        day: Option<u16>,
+    ) -> Result<Date, DateError> {
+        if day.is_some() && month.is_none() {
+            return Err(DateError::OrphanedComponent(DateField::Day));
+        }
+        if month.is_some() && year.is_none() {
+            return Err(DateError::OrphanedComponent(DateField::Month));
+        }
+        let date = Date { year, month, day };
+        date.validate().map(|()| date)
+    }
+    /// Builds a date from its components, reporting every component that is out of range
+    /// rather than only the first. A day is checked against its month when the month is valid,
+    /// and against 31 otherwise.
+    pub fn from_parts_checked(
+        year: Option<u16>,
+        month: Option<u16>,
+        day: Option<u16>,
+    ) -> Result<Date, Vec<DateError>> {
+        let mut errors = vec![];
+        let valid_month = month.filter(|month| (1..=12).contains(month));
+        if let Some(month) = month
+            && valid_month.is_none()
+        {
+            errors.push(DateError::InvalidCalendarDate(DateField::Month, month));
+        }
+        if let Some(day) = day {
+            let max = valid_month.map_or(31, |month| days_in_month(year, month));
+            if day == 0 || day > max {
+                errors.push(DateError::InvalidCalendarDate(DateField::Day, day));
+            }
+        }
+        if errors.is_empty() {
+            Ok(Date { year, month, day })
+        } else {
+            Err(errors)
+        }
+    }
     pub fn year(&self) -> Option<u16> {
         self.year
     }
@@ -23,103 +71,437 @@ impl Date {
     pub fn day(&self) -> Option<u16> {
         self.day
     }
-    pub fn remove_from_str(&self, s: &str, delim: char) -> String {
-        if self.day.is_none() && self.month.is_none() && self.year.is_none() {
-            s.to_string()
-        } else if self.day.is_none() && self.month.is_none() {
-            return s.replace(&self.year.unwrap().to_string(), "");
-        } else if self.day.is_none() {
-            let opt3 = format!(
-                "{}{}{}",
-                self.year.unwrap(),
-                delim,
-                if self.month.unwrap() < 10 {
-                    format!("0{}", self.month.unwrap())
-                } else {
-                    self.month.unwrap().to_string()
-                }
-            );
-            if s.contains(&opt3) {
-                return s.replace(&opt3, "");
+    fn checked(self, options: &ParseOptions) -> Result<Date, DateError> {
+        match self.validate() {
+            Ok(()) => Ok(self),
+            Err(err @ DateError::InvalidCalendarDate(DateField::Month, _))
+                if options.auto_swap_on_invalid && self.day.is_some() =>
+            {
+                let swapped = Date {
+                    year: self.year,
+                    month: self.day,
+                    day: self.month,
+                };
+                swapped.validate().map(|()| swapped).map_err(|_| err)
             }
-            let opt4 = format!(
-                "{}{}{}",
-                if self.month.unwrap() < 10 {
-                    format!("0{}", self.month.unwrap())
-                } else {
-                    self.month.unwrap().to_string()
-                },
-                delim,
-                self.year.unwrap()
-            );
-            if s.contains(&opt4) {
-                return s.replace(&opt4, "");
+            Err(err) => Err(err),
+        }
+    }
+    /// Checks the month is 1 to 12 and the day exists in its month, leap years included,
+    /// failing with the first field out of range. Missing components aren't checked.
+    pub fn validate(&self) -> Result<(), DateError> {
+        if let Some(month) = self.month {
+            if !(1..=12).contains(&month) {
+                return Err(DateError::InvalidCalendarDate(DateField::Month, month));
             }
-            let opt1 = format!("{}{}{}", self.year.unwrap(), delim, self.month.unwrap());
-            if s.contains(&opt1) {
-                return s.replace(&opt1, "");
+            if let Some(day) = self.day
+                && (day == 0 || day > days_in_month(self.year, month))
+            {
+                return Err(DateError::InvalidCalendarDate(DateField::Day, day));
             }
-            let opt2 = format!("{}{}{}", self.month.unwrap(), delim, self.year.unwrap());
-            s.replace(&opt2, "")
+        }
+        Ok(())
+    }
+    /// The date of an ISO 8601 week date, such as year 2023, week 40, weekday 4 for
+    /// 2023-10-05. Weekdays run from Monday as 1 to Sunday as 7, and week 1 is the week with the
+    /// year's first Thursday, so the date may fall in the year before or after.
+    pub fn from_iso_week(year: u16, week: u16, weekday: u16) -> Result<Date, DateError> {
+        if !(1..=iso_weeks_in_year(year)).contains(&week) {
+            return Err(DateError::InvalidCalendarDate(DateField::Week, week));
+        }
+        if !(1..=7).contains(&weekday) {
+            return Err(DateError::InvalidCalendarDate(DateField::Weekday, weekday));
+        }
+        Date::from_day_number(iso_week_one(year) + (week as i64 - 1) * 7 + weekday as i64 - 1)
+    }
+    /// The ISO 8601 week-numbering year, week and weekday of a complete date.
+    pub fn iso_week(&self) -> Option<(u16, u16, u16)> {
+        self.validate().ok()?;
+        let days = self.day_number()?;
+        let weekday = Weekday::from_day_number(days) as i64 + 1;
+        // a week belongs to the year its thursday falls in
+        let (year, _, _) = civil_from_days(days - weekday + 4);
+        let year = u16::try_from(year).ok()?;
+        let week = (days - iso_week_one(year)).div_euclid(7) + 1;
+        Some((year, week as u16, weekday as u16))
+    }
+    /// Formats a complete date as an ISO 8601 week date, such as `2023-W40-4`.
+    pub fn format_iso_week(&self) -> Option<String> {
+        let (year, week, weekday) = self.iso_week()?;
+        Some(format!("{year:04}-W{week:02}-{weekday}"))
+    }
+    /// Whether `other` falls within the span this date covers, a partial date standing for
+    /// every day it doesn't rule out: `2023` covers all of 2023 and `2023-10` all of October.
+    /// `other` may be partial too, as long as it is no wider, so `2023` contains `2023-10` but
+    /// not the other way round. Orphaned components are ignored, as in [`Date::normalize`].
+    pub fn range_contains(&self, other: &Date) -> bool {
+        let (outer, inner) = (self.normalize(), other.normalize());
+        outer
+            .into_iter()
+            .zip(&inner)
+            .all(|(a, b)| a.is_none() || a == b)
+    }
+    /// Whether this is a complete date that exists in the calendar.
+    pub fn is_valid_calendar_date(&self) -> bool {
+        self.day_number().is_some() && self.validate().is_ok()
+    }
+    /// Decodes a `YYYYMMDD` integer such as `20231005`.
+    ///
+    /// Values above `99991231` can't be split into a date and give `InvalidDateFormat`, while
+    /// values that split into an impossible date give `InvalidCalendarDate`.
+    pub fn from_packed(packed: u32) -> Result<Date, DateError> {
+        if packed > 99991231 {
+            return Err(DateError::InvalidDateFormat(packed.to_string()));
+        }
+        let date = Date {
+            year: Some((packed / 10000) as u16),
+            month: Some((packed / 100 % 100) as u16),
+            day: Some((packed % 100) as u16),
+        };
+        date.validate()?;
+        Ok(date)
+    }
+    /// Parses exactly eight ASCII digits as `YYYYMMDD`, e.g. `"20231005"`.
+    pub fn from_compact_str(s: &str) -> Result<Date, DateError> {
+        if s.len() != 8 || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(DateError::InvalidDateFormat(s.to_string()));
+        }
+        Date::from_packed(s.parse()?)
+    }
+    /// Parses exactly `YYYY-MM-DD`, e.g. `"2023-10-05"`, for validating data where [`FromStr`]
+    /// would be too forgiving. Any other layout gives `FormatMismatch`.
+    ///
+    /// [`FromStr`]: std::str::FromStr
+    pub fn from_ymd_str_strict(s: &str) -> Result<Date, DateError> {
+        let matches = s.len() == 10
+            && s.bytes().enumerate().all(|(i, b)| match i {
+                4 | 7 => b == b'-',
+                _ => b.is_ascii_digit(),
+            });
+        if !matches {
+            return Err(DateError::FormatMismatch(s.to_string()));
+        }
+        Date::from_parts(
+            Some(s[..4].parse()?),
+            Some(s[5..7].parse()?),
+            Some(s[8..].parse()?),
+        )
+    }
+    /// The UTC date of a Unix timestamp in milliseconds.
+    ///
+    /// # Panics
+    ///
+    /// If the date falls outside years 0 to 65535.
+    pub fn from_timestamp_millis(ms: i64) -> Date {
+        match Date::from_day_number(ms.div_euclid(86_400_000)) {
+            Ok(date) => date,
+            Err(err) => panic!("{err}"),
+        }
+    }
+    /// Today's date in UTC, from the system clock. A clock set before 1970 gives 1970-01-01.
+    pub fn today() -> Date {
+        let since_epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        Date::from_day_number((since_epoch.as_secs() / 86400) as i64)
+            .expect("the system clock is within years 0 to 65535")
+    }
+    /// Whether this is a complete date equal to [`Date::today`], in UTC.
+    pub fn is_today(&self) -> bool {
+        *self == Date::today()
+    }
+    /// Milliseconds since the Unix epoch at midnight UTC of a complete, existing date.
+    pub fn to_timestamp_millis(&self) -> Option<i64> {
+        let days = self.day_number()?;
+        self.validate().ok()?;
+        Some(days * 86_400_000)
+    }
+    fn from_day_number(days: i64) -> Result<Date, DateError> {
+        let (year, month, day) = civil_from_days(days);
+        Ok(Date {
+            year: Some(u16::try_from(year).map_err(|_| DateError::YearOutOfRange(year))?),
+            month: Some(month),
+            day: Some(day),
+        })
+    }
+    fn complete_day_number(&self) -> Result<i64, DateError> {
+        self.day_number()
+            .ok_or(DateError::IncompleteDate((self.year, self.month, self.day)))
+    }
+    fn day_number(&self) -> Option<i64> {
+        match (self.year, self.month, self.day) {
+            (Some(year), Some(month), Some(day)) => Some(days_from_civil(year as i64, month, day)),
+            _ => None,
+        }
+    }
+    /// Enforces the invariant that a component is only present when every coarser one is, so
+    /// a day needs a month and a month needs a year. Orphaned components are dropped.
+    pub fn normalize(self) -> Date {
+        let year = self.year;
+        let month = self.month.filter(|_| year.is_some());
+        let day = self.day.filter(|_| month.is_some());
+        Date { year, month, day }
+    }
+    /// The next day, or `None` after 65535-12-31 or for a date that isn't complete and valid.
+    pub fn succ_opt(&self) -> Option<Date> {
+        self.step_days(1)
+    }
+    /// The day before, or `None` before 0000-01-01 or for a date that isn't complete and valid.
+    pub fn pred_opt(&self) -> Option<Date> {
+        self.step_days(-1)
+    }
+    fn step_days(&self, days: i64) -> Option<Date> {
+        let from = self.day_number()?;
+        self.validate().ok()?;
+        Date::from_day_number(from + days).ok()
+    }
+    /// Moves `n` whole months forward, or backward when negative, keeping the day where the
+    /// target month has it and clamping it to the month's last day otherwise, so January 31st
+    /// plus one month is the last day of February. Needs a year and month; the day is optional.
+    pub fn add_months(&self, n: i32) -> Result<Date, DateError> {
+        let (Some(year), Some(month)) = (self.year, self.month) else {
+            return Err(DateError::IncompleteDate((self.year, self.month, self.day)));
+        };
+        self.validate()?;
+        let total = year as i64 * 12 + (month - 1) as i64 + n as i64;
+        let year = total.div_euclid(12);
+        let year = u16::try_from(year).map_err(|_| DateError::YearOutOfRange(year))?;
+        let month = total.rem_euclid(12) as u16 + 1;
+        Ok(Date {
+            year: Some(year),
+            month: Some(month),
+            day: self
+                .day
+                .map(|day| day.min(days_in_month(Some(year), month))),
+        })
+    }
+    /// Moves `n` years forward, or backward when negative, keeping the month and day except
+    /// that February 29th becomes the 28th outside leap years. Needs a year.
+    pub fn add_years(&self, n: i32) -> Result<Date, DateError> {
+        let Some(year) = self.year else {
+            return Err(DateError::IncompleteDate((self.year, self.month, self.day)));
+        };
+        self.validate()?;
+        let year = year as i64 + n as i64;
+        let year = u16::try_from(year).map_err(|_| DateError::YearOutOfRange(year))?;
+        let day = match (self.month, self.day) {
+            (Some(2), Some(29)) if !is_leap_year(year) => Some(28),
+            _ => self.day,
+        };
+        Ok(Date {
+            year: Some(year),
+            day,
+            ..*self
+        })
+    }
+    /// A copy with the day replaced, without checking that the day exists.
+    pub fn with_day(self, day: u16) -> Date {
+        Date {
+            day: Some(day),
+            ..self
+        }
+    }
+    /// A copy with the day replaced, as long as the month (and year, for leap days) has it.
+    pub fn checked_with_day(self, day: u16) -> Result<Date, DateError> {
+        if self.month.is_none() {
+            return Err(DateError::IncompleteDate((self.year, self.month, self.day)));
+        }
+        let date = self.with_day(day);
+        date.validate()?;
+        Ok(date)
+    }
+    /// This date with its year counted in `era`; see [`SignedDate::new`].
+    pub fn with_era(self, era: Era) -> Result<SignedDate, DateError> {
+        SignedDate::new(self, era)
+    }
+    /// Clamps the year into `min..=max`, leaving the month and day as they are.
+    pub fn clamp_year(self, min: u16, max: u16) -> Date {
+        Date {
+            year: self.year.map(|year| year.clamp(min, max)),
+            ..self
+        }
+    }
+    /// Drops the components finer than `precision`, e.g. the day of a date truncated to
+    /// [`DatePrecision::Month`].
+    pub fn truncate_to(&self, precision: DatePrecision) -> Date {
+        Date {
+            year: self.year,
+            month: self.month.filter(|_| precision != DatePrecision::Year),
+            day: self.day.filter(|_| precision == DatePrecision::Day),
+        }
+    }
+    /// The day of the year, starting from 1 on January 1st; `None` unless the date is complete
+    /// and exists.
+    pub fn ordinal(&self) -> Option<u16> {
+        self.validate().ok()?;
+        let start_of_year = days_from_civil(self.year? as i64, 1, 1);
+        Some((self.day_number()? - start_of_year + 1) as u16)
+    }
+    /// Days left in the year after this one, so 0 on December 31st.
+    pub fn days_remaining_in_year(&self) -> Option<u16> {
+        Some(days_in_year(self.year?) - self.ordinal()?)
+    }
+    /// How much of the year has passed at the start of this day, from 0.0 on January 1st.
+    /// `None` unless the date is complete and exists.
+    pub fn year_fraction(&self) -> Option<f64> {
+        Some(f64::from(self.ordinal()? - 1) / f64::from(days_in_year(self.year?)))
+    }
+    /// Formats a complete date the way RFC 2822 writes dates, e.g. `Thu, 05 Oct 2023`.
+    pub fn to_rfc2822_date(&self) -> Option<String> {
+        let weekday = self.weekday().ok()?;
+        let month = MONTH_NAMES.get(self.month? as usize - 1)?;
+        Some(format!(
+            "{}, {:02} {}{} {:04}",
+            weekday.abbreviation(),
+            self.day?,
+            month[..1].to_ascii_uppercase(),
+            &month[1..3],
+            self.year?
+        ))
+    }
+    /// Every day from this date to `end` inclusive; see [`date_range`].
+    pub fn iter_days_until(&self, end: &Date) -> impl Iterator<Item = Date> + use<> {
+        date_range(self, end)
+    }
+    /// The meteorological season of the month, where winter is December to February in the
+    /// north and June to August in the south.
+    pub fn season(&self, hemisphere: Hemisphere) -> Option<Season> {
+        let northern = match self.month? {
+            12 | 1 | 2 => Season::Winter,
+            3..=5 => Season::Spring,
+            6..=8 => Season::Summer,
+            9..=11 => Season::Autumn,
+            _ => return None,
+        };
+        Some(match hemisphere {
+            Hemisphere::Northern => northern,
+            Hemisphere::Southern => match northern {
+                Season::Winter => Season::Summer,
+                Season::Spring => Season::Autumn,
+                Season::Summer => Season::Winter,
+                Season::Autumn => Season::Spring,
+            },
+        })
+    }
+    /// Moves a date falling on a weekend to the nearest weekday, Saturday back to Friday and
+    /// Sunday on to Monday. Weekdays are returned unchanged.
+    pub fn nearest_weekday(&self) -> Result<Date, DateError> {
+        self.shift_weekend(WeekendShift::Nearest)
+    }
+    /// Moves a date falling on a weekend to a weekday in the direction given by `shift`.
+    pub fn shift_weekend(&self, shift: WeekendShift) -> Result<Date, DateError> {
+        let days = self.complete_day_number()?;
+        self.validate()?;
+        let offset = match (Weekday::from_day_number(days), shift) {
+            (Weekday::Saturday, WeekendShift::Nearest | WeekendShift::Backward) => -1,
+            (Weekday::Saturday, WeekendShift::Forward) => 2,
+            (Weekday::Sunday, WeekendShift::Nearest | WeekendShift::Forward) => 1,
+            (Weekday::Sunday, WeekendShift::Backward) => -2,
+            _ => 0,
+        };
+        Date::from_day_number(days + offset)
+    }
+    /// The first date strictly after this one falling on `target`, so a Monday moves a week on.
+    pub fn next_weekday(&self, target: Weekday) -> Result<Date, DateError> {
+        let days = self.complete_day_number()?;
+        self.validate()?;
+        Date::from_day_number(days + 1)?.roll_forward_to_weekday(target)
+    }
+    /// This date if it falls on `target`, otherwise the first date after it that does.
+    pub fn roll_forward_to_weekday(&self, target: Weekday) -> Result<Date, DateError> {
+        let days = self.complete_day_number()?;
+        self.validate()?;
+        let offset = (target as i64 - Weekday::from_day_number(days) as i64).rem_euclid(7);
+        Date::from_day_number(days + offset)
+    }
+    /// The day of the week. Fails unless the date is complete and exists.
+    pub fn weekday(&self) -> Result<Weekday, DateError> {
+        let days = self.complete_day_number()?;
+        self.validate()?;
+        Ok(Weekday::from_day_number(days))
+    }
+    /// The ISO 8601 day of the week, Monday being 1 and Sunday 7, as in chrono's
+    /// `number_from_monday`. `None` unless the date is complete and exists.
+    pub fn weekday_num_iso(&self) -> Option<u8> {
+        Some(self.weekday().ok()? as u8 + 1)
+    }
+    /// The day of the week counted from Sunday as 0 to Saturday as 6, as C's `tm_wday`.
+    /// `None` unless the date is complete and exists.
+    pub fn weekday_num_sunday(&self) -> Option<u8> {
+        Some(self.weekday_num_iso()? % 7)
+    }
+    /// Formats a complete, existing date as `YY{sep}MM{sep}DD` with the last two digits of the
+    /// year, as in `23/10/05`.
+    pub fn to_short_string(&self, sep: char) -> Option<String> {
+        let (year, month, day) = (self.year?, self.month?, self.day?);
+        self.validate().ok()?;
+        Some(format!("{:02}{sep}{month:02}{sep}{day:02}", year % 100))
+    }
+    /// Describes this date relative to `reference`, e.g. `today`, `3 days ago` or `in 2 weeks`.
+    ///
+    /// Offsets under a week are given in days, under 30 days in weeks, under 365 days in
+    /// months of 30 days and anything longer in years of 365 days, always rounded down.
+    /// Returns `None` unless both dates are complete and exist.
+    pub fn humanize_since(&self, reference: &Date) -> Option<String> {
+        self.validate().ok()?;
+        reference.validate().ok()?;
+        let diff = self.day_number()? - reference.day_number()?;
+        if diff == 0 {
+            return Some("today".to_string());
+        }
+        let days = diff.abs();
+        let (count, unit) = if days < 7 {
+            (days, "day")
+        } else if days < 30 {
+            (days / 7, "week")
+        } else if days < 365 {
+            (days / 30, "month")
         } else {
-            let opt3 = format!(
-                "{}{}{}{}{}",
-                self.year.unwrap(),
-                delim,
-                if self.month.unwrap() < 10 {
-                    format!("0{}", self.month.unwrap())
-                } else {
-                    self.month.unwrap().to_string()
-                },
-                delim,
-                if self.day.unwrap() < 10 {
-                    format!("0{}", self.day.unwrap())
-                } else {
-                    self.day.unwrap().to_string()
+            (days / 365, "year")
+        };
+        let plural = if count == 1 { "" } else { "s" };
+        if diff < 0 {
+            Some(format!("{count} {unit}{plural} ago"))
+        } else {
+            Some(format!("in {count} {unit}{plural}"))
+        }
+    }
+    pub fn remove_from_str(&self, s: &str, delim: char) -> String {
+        match (self.year, self.month, self.day) {
+            (Some(year), None, None) => s.replace(&year.to_string(), ""),
+            (Some(year), Some(month), None) => {
+                let opt3 = format!("{year}{delim}{month:02}");
+                if s.contains(&opt3) {
+                    return s.replace(&opt3, "");
                 }
-            );
-            if s.contains(&opt3) {
-                return s.replace(&opt3, "");
+                let opt4 = format!("{month:02}{delim}{year}");
+                if s.contains(&opt4) {
+                    return s.replace(&opt4, "");
+                }
+                let opt1 = format!("{year}{delim}{month}");
+                if s.contains(&opt1) {
+                    return s.replace(&opt1, "");
+                }
+                let opt2 = format!("{month}{delim}{year}");
+                s.replace(&opt2, "")
             }
-            let opt4 = format!(
-                "{}{}{}{}{}",
-                if self.month.unwrap() < 10 {
-                    format!("0{}", self.month.unwrap())
-                } else {
-                    self.month.unwrap().to_string()
-                },
-                delim,
-                if self.day.unwrap() < 10 {
-                    format!("0{}", self.day.unwrap())
-                } else {
-                    self.day.unwrap().to_string()
-                },
-                delim,
-                self.year.unwrap()
-            );
-            if s.contains(&opt4) {
-                return s.replace(&opt4, "");
-            }
-            let opt1 = format!(
-                "{}{}{}{}{}",
-                self.year.unwrap(),
-                delim,
-                self.month.unwrap(),
-                delim,
-                self.day.unwrap()
-            );
-            if s.contains(&opt1) {
-                return s.replace(&opt1, "");
-            }
-            let opt2 = format!(
-                "{}{}{}{}{}",
-                self.month.unwrap(),
-                delim,
-                self.day.unwrap(),
-                delim,
-                self.year.unwrap()
-            );
-            s.replace(&opt2, "")
+            (Some(year), Some(month), Some(day)) => {
+                let opt3 = format!("{year}{delim}{month:02}{delim}{day:02}");
+                if s.contains(&opt3) {
+                    return s.replace(&opt3, "");
+                }
+                let opt4 = format!("{month:02}{delim}{day:02}{delim}{year}");
+                if s.contains(&opt4) {
+                    return s.replace(&opt4, "");
+                }
+                let opt1 = format!("{year}{delim}{month}{delim}{day}");
+                if s.contains(&opt1) {
+                    return s.replace(&opt1, "");
+                }
+                let opt2 = format!("{month}{delim}{day}{delim}{year}");
+                s.replace(&opt2, "")
+            }
+            _ => s.to_string(),
         }
     }
 }
@@ -133,358 +515,5097 @@ impl IntoIterator for &'_ Date {
     }
 }
 
-impl PartialOrd for Date {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        for (a, b) in self.into_iter().zip(other.into_iter()) {
-            if a.is_none() && b.is_some() {
-                return Some(std::cmp::Ordering::Less);
-            } else if a.is_some() && b.is_none() {
-                return Some(std::cmp::Ordering::Greater);
-            } else if let (Some(a), Some(b)) = (a, b) {
-                match a.cmp(&b) {
-                    std::cmp::Ordering::Less => return Some(std::cmp::Ordering::Less),
-                    std::cmp::Ordering::Greater => return Some(std::cmp::Ordering::Greater),
-                    std::cmp::Ordering::Equal => continue,
-                }
-            }
-        }
-        Some(std::cmp::Ordering::Equal)
-    }
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Era {
+    Bce,
+    Ce,
 }
 
-fn is_separator(ch: &char) -> bool {
-    matches!(ch, '-' | '/' | '_' | ' ' | '.')
+/// A [`Date`] whose year is counted within an [`Era`], for years before the common era that a
+/// `u16` year can't express.
+///
+/// Years follow the historical convention with no year zero, so 1 BCE is followed directly by
+/// 1 CE and [`SignedDate::signed_year`] gives -1 then 1.
+#[derive(Debug, Clone, Copy)]
+pub struct SignedDate {
+    date: Date,
+    era: Era,
 }
 
-pub fn find_dates(s: &str) -> Vec<Result<Date, DateError>> {
-    let mut date_holders = DateHolders::new();
-    let mut date_holder = DateHolder::new();
-    let mut curr_part = Part::new();
-    for letter in s.chars() {
-        if is_separator(&letter) || letter.is_ascii_digit() {
-            if letter.is_ascii_digit() {
-                curr_part.push(letter);
-            } else if !curr_part.is_empty() {
-                date_holder.add_date_part(&mut curr_part);
-            }
-        } else if date_holder.len() >= 2 {
-            date_holders.push(&mut date_holder);
-        } else if !date_holder.is_empty() {
-            date_holder.truncate();
-            curr_part.truncate();
+impl SignedDate {
+    /// Fails with `InvalidCalendarDate` for year 0, which neither era has.
+    pub fn new(date: Date, era: Era) -> Result<Self, DateError> {
+        if date.year == Some(0) {
+            return Err(DateError::InvalidCalendarDate(DateField::Year, 0));
+        }
+        Ok(Self { date, era })
+    }
+    pub fn date(&self) -> Date {
+        self.date
+    }
+    pub fn era(&self) -> Era {
+        self.era
+    }
+    /// The year with years before the common era negative, e.g. -44 for 44 BCE.
+    pub fn signed_year(&self) -> Option<i32> {
+        let year = self.date.year? as i32;
+        match self.era {
+            Era::Bce => Some(-year),
+            Era::Ce => Some(year),
         }
     }
-    if !date_holder.is_empty() {
-        date_holder.add_date_part(&mut curr_part);
-        date_holders.push(&mut date_holder);
+    // the era only counts through the year, so two dates without one are equal whatever era
+    fn key(&self) -> (Option<i32>, Option<u16>, Option<u16>) {
+        (self.signed_year(), self.date.month, self.date.day)
     }
-    date_holders.as_dates()
 }
 
-pub fn find_last_date(s: &str) -> Result<Date, DateError> {
-    match find_dates(s).pop() {
-        Some(date_result) => date_result,
-        None => Err(DateError::NoDatesFound(s.to_string())),
+impl PartialEq for SignedDate {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
     }
 }
 
-#[derive(Clone, Debug)]
-struct Part(Vec<char>);
-
-impl Part {
-    fn new() -> Self {
-        Self(vec![])
-    }
-    fn truncate(&mut self) {
-        self.0.truncate(0);
-    }
-    fn push(&mut self, ch: char) {
-        self.0.push(ch);
-    }
-    fn is_empty(&self) -> bool {
-        self.0.is_empty()
-    }
-    fn to_u16(&self) -> Result<u16, DateError> {
-        Ok(self
-            .0
-            .iter()
-            .enumerate()
-            .filter_map(|(i, ch)| {
-                if i == 0 && *ch == '0' {
-                    None
-                } else {
-                    Some(*ch)
-                }
-            })
-            .collect::<String>()
-            .parse::<u16>()?)
+impl PartialOrd for SignedDate {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.key().partial_cmp(&other.key())
     }
 }
-impl Display for Part {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for ch in self.0.iter() {
-            write!(f, "{ch}")?;
-        }
-        Ok(())
+
+/// Parses a year written with an era marker, such as `44 BC`, `44 BCE`, `AD 800` or `2023 CE`.
+pub fn parse_era_year(s: &str) -> Result<SignedDate, DateError> {
+    let invalid = || DateError::InvalidDateFormat(s.to_string());
+    let words = s.split_whitespace().collect::<Vec<_>>();
+    let (year, era) = match words[..] {
+        [year, era] | [era, year] if year.bytes().all(|b| b.is_ascii_digit()) => (year, era),
+        _ => return Err(invalid()),
+    };
+    let era = match era.to_ascii_uppercase().as_str() {
+        "BC" | "BCE" => Era::Bce,
+        "AD" | "CE" => Era::Ce,
+        _ => return Err(invalid()),
+    };
+    Date {
+        year: Some(year.parse::<u16>()?),
+        month: None,
+        day: None,
     }
+    .with_era(era)
 }
-#[derive(Clone, Debug)]
-struct DateHolder {
-    holding: Vec<Part>,
+
+/// A time of day to a whole second, for pairing with a [`Date`].
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy)]
+pub struct Time {
+    hour: u8,
+    minute: u8,
+    second: u8,
 }
-impl Display for DateHolder {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut str = String::new();
-        for part in self.holding.iter() {
-            str.push_str((part.to_string() + " ").as_str());
+
+impl Time {
+    /// Fails with `InvalidTime` unless the hour is 0 to 23, the minute 0 to 59 and the second
+    /// 0 to 60, allowing for a leap second.
+    pub fn new(hour: u8, minute: u8, second: u8) -> Result<Time, DateError> {
+        if hour > 23 || minute > 59 || second > 60 {
+            return Err(DateError::InvalidTime((hour, minute, second)));
         }
-        str.pop(); // remove last space
-        write!(f, "{}", str)
-    }
-}
-impl DateHolder {
-    fn new() -> Self {
-        Self { holding: vec![] }
+        Ok(Time {
+            hour,
+            minute,
+            second,
+        })
     }
-    fn add_date_part(&mut self, part: &mut Part) {
-        self.holding.push(part.clone());
-        part.truncate();
+    pub fn hour(&self) -> u8 {
+        self.hour
     }
-    fn truncate(&mut self) {
-        self.holding.truncate(0);
+    pub fn minute(&self) -> u8 {
+        self.minute
     }
-    fn is_empty(&self) -> bool {
-        self.holding.is_empty()
+    pub fn second(&self) -> u8 {
+        self.second
     }
-    fn len(&self) -> usize {
-        self.holding.len()
+}
+
+impl Display for Time {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)
     }
-    fn as_date(&self) -> Result<Date, DateError> {
-        let mut year = None;
-        let mut month = None;
-        let mut day = None;
-        match self.holding.len() {
-            2 => {
-                let opt1 = self.holding[0].to_u16()?;
-                let opt2 = self.holding[1].to_u16()?;
-                if opt1 > 12 {
-                    year = Some(opt1);
-                    month = Some(opt2);
-                } else if opt2 > 12 {
-                    month = Some(opt1);
-                    year = Some(opt2);
-                } else {
-                    return Err(DateError::UndecidedDate((Some(opt1), Some(opt2), None)));
-                }
-            }
-            3 => {
-                let opt1 = self.holding[0].to_u16()?;
-                let opt2 = self.holding[1].to_u16()?;
-                let opt3 = self.holding[2].to_u16()?;
-                // if first date is greater than 12, it's year
-                if opt1 > 12 {
-                    year.replace(opt1);
-                    month.replace(opt2);
-                    day.replace(opt3);
-                    // if last date is greater than 12, it's year
-                } else if opt3 > 12 && opt1 > 12 {
-                    day.replace(opt2);
-                    month.replace(opt1);
-                    year.replace(opt3);
-                    // if middle date is greater than 12, it's day
-                } else if opt2 > 12 {
-                    month.replace(opt1);
-                    day.replace(opt2);
-                    year.replace(opt3);
-                    // if all dates are equal it doesnt matter
-                } else if opt1 == opt2 && opt2 == opt3 {
-                    year.replace(opt1);
-                    month.replace(opt2);
-                    day.replace(opt3);
-                    // otherwise undecided
-                } else {
-                    return Err(DateError::UndecidedDate((
-                        Some(opt1),
-                        Some(opt2),
-                        Some(opt3),
-                    )));
-                }
-            }
-            _ => return Err(DateError::InvalidDateFormat(self.to_string())),
+}
+
+/// Parses `HH:MM:SS` or `HH:MM`, the seconds then being 0.
+impl std::str::FromStr for Time {
+    type Err = DateError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = s.split(':').collect();
+        let is_field = |field: &&str| field.len() == 2 && field.bytes().all(|b| b.is_ascii_digit());
+        if !(2..=3).contains(&fields.len()) || !fields.iter().all(is_field) {
+            return Err(DateError::InvalidDateFormat(s.to_string()));
         }
-        Ok(Date { year, month, day })
+        let second = fields.get(2).map_or(Ok(0), |field| field.parse())?;
+        Time::new(fields[0].parse()?, fields[1].parse()?, second)
     }
 }
 
-#[derive(Debug)]
-struct DateHolders(Vec<DateHolder>);
-impl DateHolders {
-    fn new() -> Self {
-        Self(vec![])
-    }
-    fn push(&mut self, date_holder: &mut DateHolder) {
-        self.0.push(date_holder.clone());
-        date_holder.truncate();
-    }
+/// `a - b` is the signed number of days from `b` to `a`, or `IncompleteDate` if either is
+/// missing a year, month or day and `InvalidCalendarDate` if either does not exist.
+impl std::ops::Sub for Date {
+    type Output = Result<i64, DateError>;
 
-    fn as_dates(&self) -> Vec<Result<Date, DateError>> {
-        let mut dates = vec![];
-        for holder in self.0.iter() {
-            dates.push(holder.as_date());
-        }
-        dates
+    fn sub(self, other: Date) -> Self::Output {
+        let (days, other_days) = (self.complete_day_number()?, other.complete_day_number()?);
+        self.validate()?;
+        other.validate()?;
+        Ok(days - other_days)
     }
 }
 
-#[derive(Debug, PartialEq)]
-pub enum DateError {
-    NoDatesFound(String),
-    UndecidedDate((Option<u16>, Option<u16>, Option<u16>)),
-    InvalidDateFormat(String),
-    ParseIntError(ParseIntError),
+/// A day of the week, Monday first as in ISO 8601.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
 }
 
-impl Display for DateError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl Weekday {
+    // 1970-01-01, day zero, was a thursday
+    fn from_day_number(days: i64) -> Weekday {
+        const WEEKDAYS: [Weekday; 7] = [
+            Weekday::Monday,
+            Weekday::Tuesday,
+            Weekday::Wednesday,
+            Weekday::Thursday,
+            Weekday::Friday,
+            Weekday::Saturday,
+            Weekday::Sunday,
+        ];
+        WEEKDAYS[(days + 3).rem_euclid(7) as usize]
+    }
+
+    fn abbreviation(self) -> &'static str {
         match self {
-            DateError::NoDatesFound(msg) => write!(f, "No dates found from {}", msg),
-            DateError::UndecidedDate(msg) => write!(
-                f,
-                "unable to determine date from values: {:?} {:?} {:?}",
-                msg.0, msg.1, msg.2
-            ),
-            DateError::InvalidDateFormat(msg) => write!(f, "Invalid date format from {}", msg),
-            DateError::ParseIntError(err) => write!(f, "{err}",),
+            Weekday::Monday => "Mon",
+            Weekday::Tuesday => "Tue",
+            Weekday::Wednesday => "Wed",
+            Weekday::Thursday => "Thu",
+            Weekday::Friday => "Fri",
+            Weekday::Saturday => "Sat",
+            Weekday::Sunday => "Sun",
         }
     }
 }
 
-impl From<ParseIntError> for DateError {
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+/// Which half of the world [`Date::season`] is for.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Hemisphere {
+    Northern,
+    Southern,
+}
+
+/// Where [`Date::shift_weekend`] moves a Saturday or Sunday.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum WeekendShift {
+    /// Saturday to the Friday before, Sunday to the Monday after.
+    #[default]
+    Nearest,
+    /// Both to the following Monday.
+    Forward,
+    /// Both to the preceding Friday.
+    Backward,
+}
+
+/// A signed number of days, for moving a complete, existing date with `+` and `-`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Days(pub i32);
+
+impl std::ops::Add<Days> for Date {
+    type Output = Result<Date, DateError>;
+
+    fn add(self, days: Days) -> Self::Output {
+        let day_number = self.complete_day_number()?;
+        self.validate()?;
+        Date::from_day_number(day_number + days.0 as i64)
+    }
+}
+
+impl std::ops::Sub<Days> for Date {
+    type Output = Result<Date, DateError>;
+
+    fn sub(self, days: Days) -> Self::Output {
+        let day_number = self.complete_day_number()?;
+        self.validate()?;
+        Date::from_day_number(day_number - days.0 as i64)
+    }
+}
+
+/// ISO 8601 output at the date's own precision: `2023-10-05`, `2023-10` or `2023`, with
+/// components zero-padded. Orphaned components are left out, as in [`Date::normalize`], so a
+/// date without a year renders as an empty string.
+impl Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let date = self.normalize();
+        if let Some(year) = date.year {
+            write!(f, "{year:04}")?;
+        }
+        if let Some(month) = date.month {
+            write!(f, "-{month:02}")?;
+        }
+        if let Some(day) = date.day {
+            write!(f, "-{day:02}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a string that is one date and nothing else, such as `2023-10-05` or `5 Oct 2023`.
+/// A bare four-digit year reads as a year-only date, so every non-empty [`Display`] output
+/// parses back. Surrounding text or a second date gives `InvalidDateFormat`; use
+/// [`find_dates`] to pick dates out of prose.
+impl std::str::FromStr for Date {
+    type Err = DateError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() == 4 && s.bytes().all(|b| b.is_ascii_digit()) {
+            return Ok(Date {
+                year: Some(s.parse()?),
+                ..Date::default()
+            });
+        }
+        let options = ParseOptions::default();
+        let mut tokens = scan(s, &options).into_iter();
+        match (tokens.next(), tokens.next()) {
+            (Some(token), None) if token.covers(s) => token.date_holder.as_date_with(&options),
+            _ => Err(DateError::InvalidDateFormat(s.to_string())),
+        }
+    }
+}
+
+/// Serializes as the [`Display`] string: `"2023-10-05"`, `"2023-10"` or `"2023"`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Date {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Deserializes from a string through [`FromStr`](std::str::FromStr).
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Date {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+// how a complete, valid date falls against the day `days` after 1970-01-01
+#[cfg(any(feature = "chrono", feature = "time"))]
+impl Date {
+    fn cmp_day_number(&self, days: i64) -> Option<std::cmp::Ordering> {
+        self.validate().ok()?;
+        Some(self.day_number()?.cmp(&days))
+    }
+}
+
+/// Only a complete date can equal a `NaiveDate`.
+#[cfg(feature = "chrono")]
+impl PartialEq<chrono::NaiveDate> for Date {
+    fn eq(&self, other: &chrono::NaiveDate) -> bool {
+        self.partial_cmp(other) == Some(std::cmp::Ordering::Equal)
+    }
+}
+
+/// Only a complete date is ordered against a `NaiveDate`.
+#[cfg(feature = "chrono")]
+impl PartialOrd<chrono::NaiveDate> for Date {
+    fn partial_cmp(&self, other: &chrono::NaiveDate) -> Option<std::cmp::Ordering> {
+        use chrono::Datelike;
+        let days = days_from_civil(
+            other.year().into(),
+            other.month() as u16,
+            other.day() as u16,
+        );
+        self.cmp_day_number(days)
+    }
+}
+
+/// Only a complete date can equal a `time::Date`.
+#[cfg(feature = "time")]
+impl PartialEq<time::Date> for Date {
+    fn eq(&self, other: &time::Date) -> bool {
+        self.partial_cmp(other) == Some(std::cmp::Ordering::Equal)
+    }
+}
+
+/// Only a complete date is ordered against a `time::Date`.
+#[cfg(feature = "time")]
+impl PartialOrd<time::Date> for Date {
+    fn partial_cmp(&self, other: &time::Date) -> Option<std::cmp::Ordering> {
+        let month = u8::from(other.month()).into();
+        let days = days_from_civil(other.year().into(), month, other.day().into());
+        self.cmp_day_number(days)
+    }
+}
+
+/// Dates compare component by component from the year down, with a missing component sorting
+/// before a present one, so `2023 < 2023-10 < 2023-10-05 < 2023-11`. Orphaned components, such
+/// as a day without a month, are ignored except to break ties between otherwise equal dates.
+impl Ord for Date {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        let normalized = self.normalize().cmp_components(&other.normalize());
+        normalized.then_with(|| self.cmp_components(other))
+    }
+}
+
+impl PartialOrd for Date {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Date {
+    fn cmp_components(&self, other: &Self) -> std::cmp::Ordering {
+        for (a, b) in self.into_iter().zip(other) {
+            if a.is_none() && b.is_some() {
+                return std::cmp::Ordering::Less;
+            } else if a.is_some() && b.is_none() {
+                return std::cmp::Ordering::Greater;
+            } else if let (Some(a), Some(b)) = (a, b) {
+                match a.cmp(&b) {
+                    std::cmp::Ordering::Less => return std::cmp::Ordering::Less,
+                    std::cmp::Ordering::Greater => return std::cmp::Ordering::Greater,
+                    std::cmp::Ordering::Equal => continue,
+                }
+            }
+        }
+        std::cmp::Ordering::Equal
+    }
+}
+
+fn is_leap_year(year: u16) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+fn days_in_year(year: u16) -> u16 {
+    if is_leap_year(year) { 366 } else { 365 }
+}
+
+// without a year, february is allowed its leap day
+fn days_in_month(year: Option<u16>, month: u16) -> u16 {
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 if year.is_none_or(is_leap_year) => 29,
+        2 => 28,
+        _ => 31,
+    }
+}
+
+// the day number of the monday starting week 1, the week holding january 4th
+fn iso_week_one(year: u16) -> i64 {
+    let jan4 = days_from_civil(year as i64, 1, 4);
+    jan4 - Weekday::from_day_number(jan4) as i64
+}
+
+// 53 when december 28th, always in the last week, is a week later than usual
+fn iso_weeks_in_year(year: u16) -> u16 {
+    let dec28 = days_from_civil(year as i64, 12, 28);
+    ((dec28 - iso_week_one(year)) / 7 + 1) as u16
+}
+
+// days since 1970-01-01 in the proleptic gregorian calendar
+fn days_from_civil(year: i64, month: u16, day: u16) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = month as i64;
+    let shifted_month = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * shifted_month + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+fn civil_from_days(days: i64) -> (i64, u16, u16) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = era * 400 + year_of_era + if month <= 2 { 1 } else { 0 };
+    (year, month as u16, day as u16)
+}
+
+const SEPARATORS: [char; 5] = ['-', '/', '_', ' ', '.'];
+
+const CJK_MARKERS: [char; 3] = ['年', '月', '日'];
+
+// the zeros of the common Unicode decimal digit runs, each followed by one to nine: ASCII,
+// Arabic-Indic, Extended Arabic-Indic, the Indic scripts, Thai, Lao, Tibetan, Myanmar, Khmer,
+// Mongolian and full-width
+const DIGIT_ZEROS: [char; 19] = [
+    '0', '\u{660}', '\u{6f0}', '\u{966}', '\u{9e6}', '\u{a66}', '\u{ae6}', '\u{b66}', '\u{be6}',
+    '\u{c66}', '\u{ce6}', '\u{d66}', '\u{e50}', '\u{ed0}', '\u{f20}', '\u{1040}', '\u{17e0}',
+    '\u{1810}', '\u{ff10}',
+];
+
+// the ASCII digit a Unicode decimal digit stands for
+fn unicode_digit(ch: char) -> Option<char> {
+    DIGIT_ZEROS.iter().find_map(|zero| {
+        let value = (ch as u32)
+            .checked_sub(*zero as u32)
+            .filter(|value| *value < 10)?;
+        char::from_digit(value, 10)
+    })
+}
+
+/// Three part dates starting with a year, such as `2023-05-06`, are always read as ISO 8601
+/// year, month, day, even when the month and day could be swapped.
+pub fn find_dates(s: &str) -> Vec<Result<Date, DateError>> {
+    find_dates_with(s, &ParseOptions::default())
+}
+
+pub fn find_dates_with(s: &str, options: &ParseOptions) -> Vec<Result<Date, DateError>> {
+    resolve(s, options).0
+}
+
+/// A reusable date finder, built once from its options and shared freely between threads.
+/// Each call borrows the options rather than copying them.
+#[derive(Debug, Clone, Default)]
+pub struct DateParser {
+    options: ParseOptions,
+}
+
+impl DateParser {
+    pub fn new(options: ParseOptions) -> Self {
+        Self { options }
+    }
+    pub fn options(&self) -> &ParseOptions {
+        &self.options
+    }
+    /// Like [`find_dates_with`] with this parser's options.
+    pub fn find_dates(&self, s: &str) -> Vec<Result<Date, DateError>> {
+        find_dates_with(s, &self.options)
+    }
+}
+
+/// The configuration a scan actually ran with, for logging how dates were found.
+#[derive(Debug, Clone)]
+pub struct ResolvedConfig {
+    pub options: ParseOptions,
+    /// Characters that separated date parts.
+    pub separators: Vec<char>,
+    /// The order learned from the input when `options.learn_order` found one.
+    pub learned_order: Option<DateOrder>,
+}
+
+impl ResolvedConfig {
+    /// The order ambiguous dates were resolved in, either given or learned.
+    pub fn order(&self) -> Option<DateOrder> {
+        self.options.order.or(self.learned_order)
+    }
+}
+
+/// Like [`find_dates_with`], also returning the configuration that was applied.
+pub fn find_dates_resolved(
+    s: &str,
+    options: &ParseOptions,
+) -> (Vec<Result<Date, DateError>>, ResolvedConfig) {
+    let (dates, learned) = resolve(s, options);
+    let config = ResolvedConfig {
+        options: options.clone(),
+        separators: Scanner::new(options).separators(),
+        learned_order: learned,
+    };
+    (dates, config)
+}
+
+fn resolve(s: &str, options: &ParseOptions) -> (Vec<Result<Date, DateError>>, Option<DateOrder>) {
+    let tokens = scan(s, options);
+    let learned = if options.learn_order && options.order.is_none() {
+        majority_order(&tokens)
+    } else {
+        None
+    };
+    let dates = tokens
+        .iter()
+        .map(|token| match learned {
+            Some(order) => {
+                token.as_date_ordered(options, token.date_holder.implied_order().or(Some(order)))
+            }
+            None => token.as_date_with(options),
+        })
+        .collect();
+    (dates, learned)
+}
+
+/// The order most dates in `s` are written in, judged only by dates that give their order away
+/// through a written out year and a day above 12. `None` when no order leads.
+pub fn detect_dominant_format(s: &str) -> Option<DateOrder> {
+    majority_order(&scan(s, &ParseOptions::default()))
+}
+
+// the order implied by most tokens, if any single order leads
+fn majority_order(tokens: &[Token]) -> Option<DateOrder> {
+    let mut counts = [
+        (DateOrder::Ymd, 0),
+        (DateOrder::Dmy, 0),
+        (DateOrder::Mdy, 0),
+    ];
+    for order in tokens
+        .iter()
+        .filter_map(|token| token.date_holder.implied_order())
+    {
+        if let Some((_, count)) = counts.iter_mut().find(|(o, _)| *o == order) {
+            *count += 1;
+        }
+    }
+    counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    if counts[0].1 > counts[1].1 {
+        Some(counts[0].0)
+    } else {
+        None
+    }
+}
+
+/// Handling of `early`, `mid` and `late` in prose such as `mid-October 2023` or `late 2023`.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum QualifierPolicy {
+    /// The qualifiers are ordinary words, and a bare year such as `2023` isn't a date.
+    #[default]
+    Ignore,
+    /// Qualified dates keep the precision they were written with: `mid-October 2023` is
+    /// `2023-10` and `late 2023` is `2023`.
+    KeepPrecision,
+    /// Qualified dates get the next component filled in. For a month, `early`, `mid` and `late`
+    /// are the 5th, 15th and 25th; for a year they are February, June and October.
+    Representative,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Qualifier {
+    Early,
+    Mid,
+    Late,
+}
+
+impl Qualifier {
+    fn from_word(word: &str) -> Option<Self> {
+        match word {
+            "early" => Some(Qualifier::Early),
+            "mid" => Some(Qualifier::Mid),
+            "late" => Some(Qualifier::Late),
+            _ => None,
+        }
+    }
+    fn refine(self, date: Date, policy: QualifierPolicy) -> Date {
+        if policy != QualifierPolicy::Representative || date.day.is_some() {
+            return date;
+        }
+        let (day, month) = match self {
+            Qualifier::Early => (5, 2),
+            Qualifier::Mid => (15, 6),
+            Qualifier::Late => (25, 10),
+        };
+        match date.month {
+            Some(_) => Date {
+                day: Some(day),
+                ..date
+            },
+            None => Date {
+                month: Some(month),
+                ..date
+            },
+        }
+    }
+}
+
+/// Options for [`find_dates_with`]; the default matches [`find_dates`].
+#[derive(Debug, Clone)]
+pub struct ParseOptions {
+    pub dot_policy: DotPolicy,
+    /// Skip dot or comma grouped numbers such as `1.000.000` and `1,000,000` instead of treating
+    /// them as dates.
+    pub skip_thousands_groups: bool,
+    /// Component order assumed for three part dates, unless a clearly written year says
+    /// otherwise.
+    pub order: Option<DateOrder>,
+    /// Scan twice, resolving dates that don't give their order away, such as `05-10-23`, in the
+    /// order most other dates in the input imply. Ignored when `order` is set.
+    pub learn_order: bool,
+    /// Year given to two part dates with no year of their own, such as `10-05` in a log, when
+    /// `order` says which part is the month.
+    pub assume_year: Option<u16>,
+    /// Retry a date whose month is impossible with its month and day swapped.
+    pub auto_swap_on_invalid: bool,
+    /// How `early`, `mid` and `late` before a month name or a year are treated.
+    pub qualifiers: QualifierPolicy,
+    /// Also separate parts with the CJK year, month and day markers, as in `2023年10月5日`.
+    pub cjk_markers: bool,
+    /// Read a year written twice in a row, as in the typo `2023-2023-10`, as if it were written
+    /// once, rather than failing with `DuplicateComponent`.
+    pub collapse_duplicate_year: bool,
+    /// Also separate parts with commas, as in `October 5, 2023`. Pair with
+    /// `skip_thousands_groups` so numbers such as `1,234,567` aren't taken for dates.
+    pub comma_separator: bool,
+    /// Let spaces separate parts, as in `2023 10 05`. When false a space ends a date like any
+    /// other text, so dates need a tighter separator to be found. On by default.
+    pub bind_on_whitespace: bool,
+    /// Read two digit years at or above the pivot as 19xx and those below it as 20xx, so with
+    /// the default of 69 `99` is 1999 and `20` is 2020. `None` keeps such years as written.
+    pub two_digit_year_pivot: Option<u16>,
+    /// Read a run of eight digits with no separators, as in `20231005_report.pdf`, as
+    /// `YYYYMMDD`, and one of six as `YYMMDD`.
+    pub split_compact_dates: bool,
+    /// With `split_compact_dates`, pass over a run that doesn't split into a real date, such as
+    /// `20231345`, rather than failing with `InvalidCalendarDate`.
+    pub skip_invalid_compact: bool,
+    /// Read a four digit number standing alone, as in `founded in 2023`, as a year-only date.
+    pub lone_years: bool,
+    /// Also read decimal digits from other scripts, such as the full-width `２０２３` or the
+    /// Arabic-Indic `٢٠٢٣`, as the ASCII digits they stand for.
+    pub unicode_digits: bool,
+    /// The characters that separate parts, by default `-`, `/`, `_`, space and `.`. The
+    /// options for dots, spaces, commas and CJK markers still apply on top, so a space or dot
+    /// listed here can be switched off by them, and a comma or marker left out switched on.
+    pub separators: Cow<'static, [char]>,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            dot_policy: DotPolicy::default(),
+            skip_thousands_groups: false,
+            order: None,
+            learn_order: false,
+            assume_year: None,
+            auto_swap_on_invalid: false,
+            qualifiers: QualifierPolicy::default(),
+            cjk_markers: false,
+            collapse_duplicate_year: false,
+            comma_separator: false,
+            bind_on_whitespace: true,
+            two_digit_year_pivot: Some(DEFAULT_YEAR_PIVOT),
+            split_compact_dates: false,
+            skip_invalid_compact: false,
+            lone_years: false,
+            unicode_digits: false,
+            separators: Cow::Borrowed(&SEPARATORS),
+        }
+    }
+}
+
+impl ParseOptions {
+    /// Options reading ambiguous dates such as `03-04-2023` day first, as across most of
+    /// Europe.
+    pub fn day_first() -> Self {
+        Self {
+            order: Some(DateOrder::Dmy),
+            ..Default::default()
+        }
+    }
+    /// Options reading ambiguous dates such as `03-04-2023` month first, as in the US.
+    pub fn month_first() -> Self {
+        Self {
+            order: Some(DateOrder::Mdy),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DateOrder {
+    Ymd,
+    Dmy,
+    Mdy,
+}
+
+/// How `.` between digits is treated, since it is as likely to be a decimal point as a date
+/// separator.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum DotPolicy {
+    /// `.` separates date parts like any other separator.
+    #[default]
+    TreatAsSeparator,
+    /// Dot-delimited groups are only dates when one of their parts is a 4-digit year.
+    RequireYearContext,
+    /// `.` is never a separator.
+    Ignore,
+}
+
+/// Like [`find_dates`], keeping only the tokens that parsed successfully.
+pub fn find_valid_dates(s: &str) -> Vec<Date> {
+    find_dates(s).into_iter().filter_map(Result::ok).collect()
+}
+
+/// The valid dates in `s` bucketed by year, the years ascending and each year's dates in the
+/// order they appear.
+pub fn group_dates_by_year(s: &str) -> BTreeMap<u16, Vec<Date>> {
+    let mut groups = BTreeMap::new();
+    for date in find_valid_dates(s) {
+        if let Some(year) = date.year {
+            groups.entry(year).or_insert_with(Vec::new).push(date);
+        }
+    }
+    groups
+}
+
+/// The distinct valid dates in `s`, in ascending order.
+pub fn find_date_set(s: &str) -> BTreeSet<Date> {
+    find_dates(s).into_iter().filter_map(Result::ok).collect()
+}
+
+/// The earliest of `dates` by their [`Ord`] order, so a year alone comes before any date
+/// within it.
+pub fn min_date(dates: &[Date]) -> Option<&Date> {
+    dates.iter().min()
+}
+
+/// The latest of `dates` by their [`Ord`] order.
+pub fn max_date(dates: &[Date]) -> Option<&Date> {
+    dates.iter().max()
+}
+
+/// [`find_dates`] over each of `inputs`, paired with the input's index. Inputs without dates
+/// are kept, with no results.
+pub fn find_dates_indexed(inputs: &[&str]) -> Vec<(usize, Vec<Result<Date, DateError>>)> {
+    inputs.iter().map(|s| find_dates(s)).enumerate().collect()
+}
+
+/// Like [`find_dates`], but stops scanning once `max` dates have been found.
+pub fn find_dates_limited(s: &str, max: usize) -> Vec<Result<Date, DateError>> {
+    iter_dates(s).take(max).collect()
+}
+
+/// Like [`find_dates`], with the byte range of `s` each date was read from, from the start of
+/// its first part to the end of its last.
+pub fn find_dates_spans(s: &str) -> Vec<(Result<Date, DateError>, Range<usize>)> {
+    scan(s, &ParseOptions::default())
+        .into_iter()
+        .map(|token| (token.date_holder.as_date(), token.span))
+        .collect()
+}
+
+/// The dates [`find_dates`] finds, scanned lazily as the iterator advances.
+pub fn iter_dates(s: &str) -> impl Iterator<Item = Result<Date, DateError>> + '_ {
+    tokens(s, Cow::Owned(ParseOptions::default())).map(|token| token.date_holder.as_date())
+}
+
+/// Parses the date `s` starts with, after any whitespace, returning it with the rest of `s`.
+/// Fails with `NoDatesFound` when `s` doesn't start with a date.
+pub fn parse_leading_date(s: &str) -> Result<(Date, &str), DateError> {
+    let start = s.len() - s.trim_start().len();
+    match tokens(s, Cow::Owned(ParseOptions::default())).next() {
+        Some(token) if token.span.start == start => {
+            Ok((token.date_holder.as_date()?, &s[token.span.end..]))
+        }
+        _ => Err(DateError::NoDatesFound(s.to_string())),
+    }
+}
+
+/// The first valid date after each occurrence of `keyword` in `s`, such as the `2023-10-05` in
+/// `Created: 2023-10-05`. A date is only looked for up to the next occurrence.
+pub fn find_dates_after_keyword(s: &str, keyword: &str) -> Vec<Date> {
+    find_dates_after_keyword_with(s, keyword, false)
+}
+
+/// Like [`find_dates_after_keyword`], optionally matching `keyword` regardless of ASCII case.
+pub fn find_dates_after_keyword_with(s: &str, keyword: &str, ignore_case: bool) -> Vec<Date> {
+    if keyword.is_empty() {
+        return find_valid_dates(s);
+    }
+    let (haystack, needle) = if ignore_case {
+        (s.to_ascii_lowercase(), keyword.to_ascii_lowercase())
+    } else {
+        (s.to_string(), keyword.to_string())
+    };
+    let starts: Vec<usize> = haystack
+        .match_indices(&needle)
+        .map(|(i, _)| i + needle.len())
+        .collect();
+    starts
+        .iter()
+        .enumerate()
+        .filter_map(|(i, start)| {
+            let end = starts
+                .get(i + 1)
+                .map_or(s.len(), |next| next - needle.len());
+            iter_dates(&s[*start..end]).find_map(Result::ok)
+        })
+        .collect()
+}
+
+/// Valid dates in a URL such as `/blog/2023/10/05/post-title`. Percent-escapes are decoded
+/// first, and `?`, `&`, `=` and `#` end a date, so query parameters are read one value at a
+/// time. A `/` ends a date too once it has three segments, or when the next segment cannot
+/// continue it, so `/2023/10/05/123-foo` is read as `2023/10/05`.
+pub fn find_dates_in_url(url: &str) -> Vec<Date> {
+    percent_decode(url)
+        .split(['?', '&', '=', '#'])
+        .flat_map(path_date_runs)
+        .flat_map(find_valid_dates)
+        .collect()
+}
+
+// the path split at each `/` that cannot be inside a date, one after a number of up to four
+// digits, before a digit and within three segments being kept
+fn path_date_runs(path: &str) -> Vec<&str> {
+    let is_number = |segment: &str| {
+        (1..=4).contains(&segment.len()) && segment.bytes().all(|byte| byte.is_ascii_digit())
+    };
+    let mut runs = vec![];
+    let (mut start, mut offset, mut segments) = (0, 0, 0);
+    let mut prev_number = false;
+    for segment in path.split('/') {
+        let continues = prev_number
+            && segments < 3
+            && segment
+                .bytes()
+                .next()
+                .is_some_and(|byte| byte.is_ascii_digit());
+        if offset > 0 && !continues {
+            runs.push(&path[start..offset - 1]);
+            (start, segments) = (offset, 0);
+        }
+        prev_number = is_number(segment);
+        segments += 1;
+        offset += segment.len() + 1;
+    }
+    runs.push(&path[start..]);
+    runs
+}
+
+// `%2F` and the like to the bytes they stand for, leaving malformed escapes as written
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escape = bytes
+            .get(i + 1..i + 3)
+            .filter(|hex| bytes[i] == b'%' && hex.iter().all(u8::is_ascii_hexdigit));
+        match escape.and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()) {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Days from `a` to `b`, negative when `b` comes first. Both dates must be complete.
+pub fn days_between(a: &Date, b: &Date) -> Result<i64, DateError> {
+    *b - *a
+}
+
+/// Every day from `start` to `end` inclusive. Empty unless both dates are complete and exist
+/// and `start` isn't after `end`.
+pub fn date_range(start: &Date, end: &Date) -> impl Iterator<Item = Date> + use<> {
+    let valid = start.validate().is_ok() && end.validate().is_ok();
+    let (start, end) = match (start.day_number(), end.day_number()) {
+        (Some(start), Some(end)) if valid => (start, end),
+        _ => (1, 0),
+    };
+    (start..=end).filter_map(|days| Date::from_day_number(days).ok())
+}
+
+/// Complete dates in `s` falling within `start..=end`; errors and partial dates are skipped.
+pub fn find_dates_in_range(s: &str, start: Date, end: Date) -> Vec<Date> {
+    let options = ParseOptions::default();
+    tokens(s, Cow::Borrowed(&options))
+        .filter_map(|token| token.date_holder.as_date().ok())
+        .filter(|date| date.day_number().is_some() && *date >= start && *date <= end)
+        .collect()
+}
+
+/// Finds ISO 8601 style intervals such as `2023-10-05/2023-10-10`, where a `/` joins two
+/// complete dates that each resolve on their own.
+pub fn find_intervals(s: &str) -> Vec<(Date, Date)> {
+    let options = ParseOptions::default();
+    scan(s, &options)
+        .iter()
+        .filter_map(|token| token.as_interval(&options))
+        .collect()
+}
+
+/// Finds ranges of dates, both ISO 8601 intervals as in [`find_intervals`] and day ranges
+/// sharing a month and year, such as `Oct 5-10, 2023` or `5-10 October 2023`.
+pub fn find_date_ranges(s: &str) -> Vec<(Date, Date)> {
+    let options = ParseOptions {
+        comma_separator: true,
+        ..Default::default()
+    };
+    scan(s, &options)
+        .iter()
+        .filter_map(|token| {
+            token
+                .as_interval(&options)
+                .or_else(|| token.as_shared_range(&options))
+        })
+        .collect()
+}
+
+/// Finds spans of years joined by a dash, such as the lifespan in `Alan Turing (1912–1954)`.
+/// Both years must be written out in four digits and the first must not come after the second.
+pub fn find_year_ranges(s: &str) -> Vec<(u16, u16)> {
+    // the en dash is only a separator here, where it can't be mistaken for prose
+    scan(&s.replace('–', "-"), &ParseOptions::default())
+        .iter()
+        .filter_map(Token::as_year_range)
+        .collect()
+}
+
+/// Like [`find_dates`], but a date equal to one already found is dropped. Errors are all kept,
+/// since each stands for a different stretch of `s` even when they read the same.
+pub fn find_unique_dates(s: &str) -> Vec<Result<Date, DateError>> {
+    let mut seen = HashSet::new();
+    find_dates(s)
+        .into_iter()
+        .filter(|result| result.as_ref().map_or(true, |date| seen.insert(*date)))
+        .collect()
+}
+
+/// Valid dates in `s` in order of first appearance, dropping any date that matches an earlier
+/// one down to `precision`.
+///
+/// Dates are compared by value, so `2023-10-05` and `10/05/2023` are duplicates whatever
+/// separators they were written with. At [`DatePrecision::Day`] a partial date such as
+/// `2023-10` only duplicates another `2023-10`, while at [`DatePrecision::Month`] it also
+/// duplicates `2023-10-05`.
+pub fn find_unique_dates_by(s: &str, precision: DatePrecision) -> Vec<Date> {
+    let mut seen = vec![];
+    let mut dates = vec![];
+    for date in find_valid_dates(s) {
+        let key = date.truncate_to(precision);
+        if !seen.contains(&key) {
+            seen.push(key);
+            dates.push(date);
+        }
+    }
+    dates
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DatePrecision {
+    Year,
+    Month,
+    Day,
+}
+
+/// Whether some arrangement of `parts` as a year, month and day (or a year and month, for two
+/// parts) is a real calendar date. This separates garbage like `[99, 99, 99]` from triples that
+/// are merely ambiguous.
+pub fn any_valid_ordering(parts: &[u16]) -> bool {
+    let candidates = match *parts {
+        [a, b] => vec![(a, b, None), (b, a, None)],
+        [a, b, c] => vec![
+            (a, b, Some(c)),
+            (a, c, Some(b)),
+            (b, a, Some(c)),
+            (b, c, Some(a)),
+            (c, a, Some(b)),
+            (c, b, Some(a)),
+        ],
+        _ => vec![],
+    };
+    candidates.into_iter().any(|(year, month, day)| {
+        Date {
+            year: Some(year),
+            month: Some(month),
+            day,
+        }
+        .validate()
+        .is_ok()
+    })
+}
+
+/// A hash map key that treats dates as equal when they match down to the given precision, for
+/// bucketing dates by year or month.
+#[derive(Debug, Clone, Copy)]
+pub struct PrecisionKey(pub Date, pub DatePrecision);
+
+impl PrecisionKey {
+    fn key(&self) -> Date {
+        self.0.truncate_to(self.1)
+    }
+}
+
+impl PartialEq for PrecisionKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for PrecisionKey {}
+
+impl std::hash::Hash for PrecisionKey {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let key = self.key();
+        key.year.hash(state);
+        key.month.hash(state);
+        key.day.hash(state);
+    }
+}
+
+/// Explains how the default rules resolve `parts` into a date, e.g. for `[2023, 10, 5]`
+/// `opt1=2023 > 12 ⇒ year first; resolved as YMD; valid`. Parts are read as written without
+/// leading zeros, so a year from 10 to 99 has two digits and is expanded around the default
+/// pivot. Meant for debugging, the wording may change.
+pub fn explain_resolution(parts: &[u16]) -> String {
+    let (reason, resolved) = match *parts {
+        [opt1, opt2] if opt2 > 99 && opt1 <= 99 => (
+            format!("opt2={opt2} has more than two digits ⇒ year last; resolved as MY"),
+            Some((opt2, opt1, None)),
+        ),
+        [opt1, opt2] if opt1 > 12 => (
+            format!("opt1={opt1} > 12 ⇒ year first; resolved as YM"),
+            Some((opt1, opt2, None)),
+        ),
+        [opt1, opt2] if opt2 > 12 => (
+            format!("opt2={opt2} > 12 ⇒ year last; resolved as MY"),
+            Some((opt2, opt1, None)),
+        ),
+        [opt1, opt2] => (
+            format!("opt1={opt1} and opt2={opt2} are both ≤ 12 ⇒ undecided"),
+            None,
+        ),
+        [opt1, opt2, opt3] if opt1 > 12 && opt3 <= 31 => (
+            format!("opt1={opt1} > 12 ⇒ year first; resolved as YMD"),
+            Some((opt1, opt2, Some(opt3))),
+        ),
+        [opt1, opt2, opt3] if opt1 > 31 => (
+            format!("opt1={opt1} > 31 ⇒ year first; resolved as YMD"),
+            Some((opt1, opt2, Some(opt3))),
+        ),
+        [opt1, opt2, opt3] if opt1 > 12 => (
+            format!("opt3={opt3} > 31 ⇒ year last, opt1={opt1} > 12 ⇒ day first; resolved as DMY"),
+            Some((opt3, opt2, Some(opt1))),
+        ),
+        [opt1, opt2, opt3] if opt2 > 12 => (
+            format!("opt2={opt2} > 12 ⇒ day in the middle; resolved as MDY"),
+            Some((opt3, opt1, Some(opt2))),
+        ),
+        [opt1, opt2, opt3] if opt1 == opt2 && opt2 == opt3 => (
+            format!("all parts are {opt1} ⇒ order doesn't matter; resolved as YMD"),
+            Some((opt1, opt2, Some(opt3))),
+        ),
+        [opt1, opt2, _] if opt1 == 0 || opt2 == 0 => (
+            format!("opt1={opt1} or opt2={opt2} is zero ⇒ no valid month and day; invalid"),
+            None,
+        ),
+        [opt1, opt2, _] => (
+            format!("opt1={opt1} and opt2={opt2} are both ≤ 12 ⇒ undecided"),
+            None,
+        ),
+        _ => (
+            format!("{} parts, expected 2 or 3 ⇒ invalid format", parts.len()),
+            None,
+        ),
+    };
+    let Some((year, month, day)) = resolved else {
+        return reason;
+    };
+    let (reason, year) = if (10..=99).contains(&year) {
+        let expanded = expand_year(year, DEFAULT_YEAR_PIVOT);
+        (
+            format!("{reason}; two-digit year {year} ⇒ {expanded}"),
+            expanded,
+        )
+    } else {
+        (reason, year)
+    };
+    let date = Date {
+        year: Some(year),
+        month: Some(month),
+        day,
+    };
+    match date.validate() {
+        Ok(()) => format!("{reason}; valid"),
+        Err(DateError::InvalidCalendarDate(field, value)) => {
+            format!("{reason}; invalid {field} {value}")
+        }
+        Err(err) => format!("{reason}; {err}"),
+    }
+}
+
+/// A token that looked like a date but does not exist on the calendar, e.g. `2023-13-01`.
+#[derive(Debug, PartialEq)]
+pub struct Diagnostic {
+    /// The text of the input the token was read from.
+    pub token: String,
+    pub error: DateError,
+}
+
+/// Like [`find_dates`], but tokens that resolved to impossible calendar dates are moved out of
+/// the results and into a separate diagnostics list.
+pub fn find_dates_with_diagnostics(s: &str) -> (Vec<Result<Date, DateError>>, Vec<Diagnostic>) {
+    let mut dates = vec![];
+    let mut diagnostics = vec![];
+    let options = ParseOptions::default();
+    for token in scan(s, &options) {
+        match token.date_holder.as_date_with(&options) {
+            Err(error @ DateError::InvalidCalendarDate(..)) => diagnostics.push(Diagnostic {
+                token: s[token.span].to_string(),
+                error,
+            }),
+            date => dates.push(date),
+        }
+    }
+    (dates, diagnostics)
+}
+
+fn scan(s: &str, options: &ParseOptions) -> Vec<Token> {
+    tokens(s, Cow::Borrowed(options)).collect()
+}
+
+// tokens are found lazily, so callers that stop early don't scan the rest of `s`
+fn tokens<'a>(s: &'a str, options: Cow<'a, ParseOptions>) -> impl Iterator<Item = Token> + 'a {
+    let mut chars = s.chars();
+    let mut scanner = Scanner::with_options(options);
+    let mut finished = false;
+    std::iter::from_fn(move || {
+        for letter in chars.by_ref() {
+            if let Some(token) = scanner.push_char(letter) {
+                return Some(token);
+            }
+        }
+        if finished {
+            return None;
+        }
+        finished = true;
+        scanner.finish()
+    })
+}
+
+#[derive(Debug)]
+struct Token {
+    date_holder: DateHolder,
+    // the separator that ended each part but the last
+    separators: Vec<char>,
+    qualifier: Option<Qualifier>,
+    // bytes of the input the parts were read from
+    span: Range<usize>,
+}
+
+impl Token {
+    fn as_interval(&self, options: &ParseOptions) -> Option<(Date, Date)> {
+        if self.date_holder.len() != 6 || self.separators.get(2) != Some(&'/') {
+            return None;
+        }
+        let (first, second) = self.date_holder.holding.split_at(3);
+        let first = DateHolder {
+            holding: first.to_vec(),
+        };
+        let second = DateHolder {
+            holding: second.to_vec(),
+        };
+        Some((
+            first.as_date_with(options).ok()?,
+            second.as_date_with(options).ok()?,
+        ))
+    }
+    fn as_year_range(&self) -> Option<(u16, u16)> {
+        let parts = &self.date_holder.holding;
+        if parts.len() != 2 || self.separators[0] != '-' || parts.iter().any(|p| p.len() != 4) {
+            return None;
+        }
+        let (start, end) = (parts[0].to_u16().ok()?, parts[1].to_u16().ok()?);
+        (start <= end).then_some((start, end))
+    }
+    // two days joined by `-` with the month named before or after them, then the year
+    fn as_shared_range(&self, options: &ParseOptions) -> Option<(Date, Date)> {
+        let parts = &self.date_holder.holding;
+        if parts.len() != 4 {
+            return None;
+        }
+        let (days_at, month) = match (parts[0].month(), parts[2].month()) {
+            (Some(month), None) => (1, month),
+            (None, Some(month)) => (0, month),
+            _ => return None,
+        };
+        let year = parts[3].to_u16().ok()?;
+        if self.separators.get(days_at) != Some(&'-') || (parts[3].len() <= 2 && year <= 31) {
+            return None;
+        }
+        let year = parts[3].to_year(options).ok()?;
+        let date = |part: &Part| {
+            let date = Date {
+                year: Some(year),
+                month: Some(month),
+                day: Some(part.to_u16().ok()?),
+            };
+            date.validate().ok().map(|()| date)
+        };
+        let (start, end) = (date(&parts[days_at])?, date(&parts[days_at + 1])?);
+        (start <= end).then_some((start, end))
+    }
+    // whether the token was read from all of `s`
+    fn covers(&self, s: &str) -> bool {
+        self.span == (0..s.len())
+    }
+    fn as_date_with(&self, options: &ParseOptions) -> Result<Date, DateError> {
+        self.as_date_ordered(options, options.order)
+    }
+    fn as_date_ordered(
+        &self,
+        options: &ParseOptions,
+        order: Option<DateOrder>,
+    ) -> Result<Date, DateError> {
+        // after a month name, a number joined to the day by `-` ends a range of days, as in
+        // `Oct 5-10, 2023`, rather than being a year
+        if let [name, _, _] = &self.date_holder.holding[..]
+            && name.month().is_some()
+            && self.separators[0] != '-'
+            && self.separators[1] == '-'
+        {
+            return Err(DateError::InvalidDateFormat(self.date_holder.to_string()));
+        }
+        let Some(qualifier) = self.qualifier else {
+            return self.date_holder.as_date_ordered(options, order);
+        };
+        // a qualifier makes a lone year a date of its own
+        let date = match &self.date_holder.holding[..] {
+            [part] if part.month().is_none() => Date {
+                year: Some(part.to_u16()?),
+                month: None,
+                day: None,
+            },
+            _ => self.date_holder.as_date_ordered(options, order)?,
+        };
+        Ok(qualifier.refine(date, options.qualifiers))
+    }
+}
+
+// numbers like `1.000.000` or `1,000,000`: up to three digits, then groups of exactly three
+fn is_thousands_group(date_holder: &DateHolder, separators: &[char]) -> bool {
+    let Some((first, groups)) = date_holder.holding.split_first() else {
+        return false;
+    };
+    let grouping = separators.first().copied();
+    matches!(grouping, Some('.' | ','))
+        && separators.iter().all(|ch| Some(*ch) == grouping)
+        && (1..=3).contains(&first.len())
+        && !groups.is_empty()
+        && groups.iter().all(|part| part.len() == 3)
+}
+
+struct Scanner<'a> {
+    options: Cow<'a, ParseOptions>,
+    date_holder: DateHolder,
+    separators: Vec<char>,
+    curr_part: Part,
+    dotted: bool,
+    // letters seen since the last non-letter
+    word: String,
+    qualifier: Option<Qualifier>,
+    // the character scanned before the current one
+    last: Option<char>,
+    // where the time of a basic datetime such as `20231005T143000` starts
+    time_at: Option<usize>,
+    // only kept for `scan_trace`
+    trace: Option<Vec<TraceEvent>>,
+    // byte offsets: of the next character, and the spans of the word, part and holder so far
+    offset: usize,
+    word_span: Range<usize>,
+    part_span: Range<usize>,
+    holder_span: Range<usize>,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(options: &'a ParseOptions) -> Self {
+        Self::with_options(Cow::Borrowed(options))
+    }
+    fn with_options(options: Cow<'a, ParseOptions>) -> Self {
+        Self {
+            options,
+            date_holder: DateHolder::new(),
+            separators: vec![],
+            curr_part: Part::new(),
+            dotted: false,
+            word: String::new(),
+            qualifier: None,
+            last: None,
+            time_at: None,
+            trace: None,
+            offset: 0,
+            word_span: 0..0,
+            part_span: 0..0,
+            holder_span: 0..0,
+        }
+    }
+    fn is_separator(&self, ch: &char) -> bool {
+        let listed = self.options.separators.contains(ch);
+        if *ch == '.' {
+            listed && self.options.dot_policy != DotPolicy::Ignore
+        } else if CJK_MARKERS.contains(ch) {
+            listed || self.options.cjk_markers
+        } else if *ch == ',' {
+            // `October 5, 2023` and `5 October, 2023` put a comma before the year
+            let after_named_day = self.date_holder.len() == 1
+                && !self.curr_part.is_empty()
+                && (self.date_holder.holding[0].month().is_some()
+                    || self.curr_part.month().is_some());
+            listed || self.options.comma_separator || after_named_day
+        } else if *ch == ' ' {
+            listed && self.options.bind_on_whitespace
+        } else {
+            listed
+        }
+    }
+    fn separators(&self) -> Vec<char> {
+        let mut separators = vec![];
+        let candidates = self.options.separators.iter().copied();
+        for ch in candidates.chain(CJK_MARKERS).chain([',']) {
+            if self.is_separator(&ch) && !separators.contains(&ch) {
+                separators.push(ch);
+            }
+        }
+        separators
+    }
+    fn push_char(&mut self, letter: char) -> Option<Token> {
+        self.push_sized(letter, letter.len_utf8())
+    }
+    // `width` is how many bytes of input `letter` stands for
+    fn push_sized(&mut self, letter: char, width: usize) -> Option<Token> {
+        // the digit table is only searched when asked for
+        let letter = if self.options.unicode_digits {
+            unicode_digit(letter).unwrap_or(letter)
+        } else {
+            letter
+        };
+        let found = self.scan_char(letter, width);
+        self.offset += width;
+        self.last = Some(letter);
+        found
+    }
+    // extends the current part with `ch`, read at `span`
+    fn extend_part(&mut self, ch: char, span: Range<usize>) {
+        if self.curr_part.is_empty() {
+            self.part_span.start = span.start;
+        }
+        self.part_span.end = span.end;
+        self.curr_part.push(ch);
+    }
+    fn scan_char(&mut self, letter: char, width: usize) -> Option<Token> {
+        let span = self.offset..self.offset + width;
+        let mut found = None;
+        // a few digits running into a date separator, as in `@10/05/2023`, are a date after all
+        let into_date =
+            letter != ' ' && self.is_separator(&letter) && (2..=5).contains(&self.curr_part.len());
+        if self.curr_part.is_epoch() && into_date {
+            self.curr_part.0.remove(0);
+            self.part_span.start += 1;
+        } else if self.curr_part.is_epoch() && !letter.is_ascii_digit() {
+            if self.curr_part.len() > 1 {
+                self.add_part();
+                found = self.take_holder();
+            }
+            self.curr_part.truncate();
+        }
+        if letter.is_alphabetic() && !self.is_separator(&letter) {
+            if self.word.is_empty() {
+                self.word_span.start = span.start;
+            }
+            self.word_span.end = span.end;
+            self.word.extend(letter.to_lowercase());
+            return found;
+        }
+        if !self.word.is_empty() {
+            found = self.end_word(Some(letter));
+        }
+        if self.is_separator(&letter) || letter.is_ascii_digit() {
+            if letter.is_ascii_digit() {
+                self.extend_part(letter, span.clone());
+            } else if !self.curr_part.is_empty() {
+                self.add_part();
+                self.separators.push(letter);
+                if letter == '.' {
+                    self.dotted = true;
+                }
+            }
+        } else {
+            found = self.interrupt();
+        }
+        // `@` directly before a run of digits marks epoch seconds, unless it's inside a word as
+        // in `deadline@2023-10-05`
+        if letter == '@'
+            && self.last.is_none_or(char::is_whitespace)
+            && self.date_holder.is_empty()
+            && self.curr_part.is_empty()
+        {
+            self.extend_part(letter, span);
+        }
+        found
+    }
+    // a month name becomes a part of its own, a qualifier waits for the date it qualifies, and
+    // any other word interrupts the date being scanned. `next` is the character that ended the
+    // word, if any.
+    fn end_word(&mut self, next: Option<char>) -> Option<Token> {
+        let word = std::mem::take(&mut self.word);
+        // an ISO 8601 basic datetime such as `20231005T143000` keeps its date, the time after
+        // the `T` being left to end on its own
+        if word == "t" && self.date_holder.is_empty() && self.curr_part.is_compact_date() {
+            let digits = std::mem::replace(&mut self.curr_part, Part::new()).0;
+            let start = self.part_span.start;
+            for range in [0..4, 4..6, 6..8] {
+                self.part_span = start + range.start..start + range.end;
+                self.curr_part = Part(digits[range].to_vec());
+                self.add_part();
+            }
+            self.time_at = Some(self.word_span.end);
+            return self.take_holder();
+        }
+        // the `W` of an ISO 8601 week date such as `2023-W40-4` starts the week part, but only
+        // straight after the year's hyphen, so `2023 W40` is a year and a word
+        if word == "w"
+            && next.is_some_and(|ch| ch.is_ascii_digit())
+            && self.curr_part.is_empty()
+            && self.date_holder.len() == 1
+            && self.date_holder.holding[0].len() == 4
+            && self.separators.last() == Some(&'-')
+        {
+            self.extend_part('w', self.word_span.clone());
+            return None;
+        }
+        if self.curr_part.is_empty() && month_from_name(&word).is_some() {
+            // a date with all three parts, or a month already, ends before the month name, as
+            // in `2023-10-05 may slip`
+            let full = self.date_holder.len() >= 3
+                || self
+                    .date_holder
+                    .holding
+                    .iter()
+                    .any(|part| part.month().is_some());
+            let found = if full { self.interrupt() } else { None };
+            self.curr_part = Part(word.chars().collect());
+            self.part_span = self.word_span.clone();
+            return found;
+        }
+        if self.options.qualifiers != QualifierPolicy::Ignore
+            && self.date_holder.is_empty()
+            && self.curr_part.is_empty()
+            && let Some(qualifier) = Qualifier::from_word(&word)
+        {
+            self.qualifier = Some(qualifier);
+            return None;
+        }
+        self.interrupt()
+    }
+    fn add_part(&mut self) {
+        if self.date_holder.is_empty() {
+            self.record(TraceEvent::TokenStarted);
+            self.holder_span.start = self.part_span.start;
+        }
+        self.holder_span.end = self.part_span.end;
+        self.record(TraceEvent::PartAdded(self.curr_part.to_string()));
+        self.date_holder.add_date_part(&mut self.curr_part);
+    }
+    fn record(&mut self, event: TraceEvent) {
+        if let Some(trace) = &mut self.trace {
+            trace.push(event);
+        }
+    }
+    fn interrupt(&mut self) -> Option<Token> {
+        // the part in progress belongs to the date, as in `2023-10-05,`
+        if !self.curr_part.is_empty() {
+            self.add_part();
+        }
+        self.split_compact_run();
+        let qualified_year = self.qualifier.is_some()
+            && self.date_holder.len() == 1
+            && self.date_holder.holding[0].month().is_none();
+        let stands_alone = self.date_holder.len() == 1
+            && self.stands_alone(&self.date_holder.holding[0], self.holder_span.start);
+        if self.date_holder.len() >= 2 || qualified_year || stands_alone {
+            return self.take_holder();
+        }
+        self.qualifier = None;
+        if !self.date_holder.is_empty() {
+            self.record(TraceEvent::HolderTruncated(self.date_holder.to_string()));
+            self.date_holder.truncate();
+            self.separators.clear();
+            self.curr_part.truncate();
+            self.dotted = false;
+        }
+        None
+    }
+    fn finish(&mut self) -> Option<Token> {
+        if !self.word.is_empty()
+            && let Some(token) = self.end_word(None)
+        {
+            return Some(token);
+        }
+        if self.curr_part.is_epoch() {
+            let mut found = None;
+            if self.curr_part.len() > 1 {
+                self.add_part();
+                found = self.take_holder();
+            }
+            self.curr_part.truncate();
+            return found;
+        }
+        if self.date_holder.is_empty() && self.qualifier.is_none() {
+            if self.stands_alone(&self.curr_part, self.part_span.start) {
+                self.add_part();
+                self.split_compact_run();
+                return self.take_holder();
+            }
+            self.curr_part.truncate();
+            return None;
+        }
+        // a trailing separator leaves no part behind
+        if !self.curr_part.is_empty() {
+            self.add_part();
+        }
+        // a month name alone, as in `see you in May`, isn't a date
+        if self.date_holder.is_empty()
+            || (self.date_holder.len() == 1 && self.date_holder.holding[0].month().is_some())
+        {
+            if !self.date_holder.is_empty() {
+                self.record(TraceEvent::HolderTruncated(self.date_holder.to_string()));
+            }
+            self.date_holder.truncate();
+            self.separators.clear();
+            self.qualifier = None;
+            return None;
+        }
+        self.split_compact_run();
+        self.take_holder()
+    }
+    // with `split_compact_dates`, a run of digits standing alone as the only part, such as
+    // `20231005` or `231005`, becomes its year, month and day. With `skip_invalid_compact` a
+    // run that isn't a real date is dropped instead.
+    fn split_compact_run(&mut self) {
+        let [run] = &self.date_holder.holding[..] else {
+            return;
+        };
+        if !self.stands_alone(run, self.holder_span.start) {
+            return;
+        }
+        let year = match run.len() {
+            8 => Part(run.0[..4].to_vec()),
+            // padded to four digits so the year is read as one, whatever the pivot
+            6 => match Part(run.0[..2].to_vec()).to_year(&self.options) {
+                Ok(year) => Part(format!("{year:04}").chars().collect()),
+                Err(_) => return,
+            },
+            _ => return,
+        };
+        let len = run.len();
+        let month = Part(run.0[len - 4..len - 2].to_vec());
+        let day = Part(run.0[len - 2..].to_vec());
+        let date = Date {
+            year: year.to_u16().ok(),
+            month: month.to_u16().ok(),
+            day: day.to_u16().ok(),
+        };
+        if self.options.skip_invalid_compact && date.validate().is_err() {
+            self.record(TraceEvent::HolderSkipped(self.date_holder.to_string()));
+            self.date_holder.truncate();
+        } else {
+            self.date_holder.holding = vec![year, month, day];
+        }
+        self.separators.clear();
+    }
+    // whether `part`, read from `start`, is a date of its own under the options, as a compact
+    // date or a year. The time of a basic datetime never is.
+    fn stands_alone(&self, part: &Part, start: usize) -> bool {
+        if self.time_at == Some(start) {
+            return false;
+        }
+        let digits = part.0.iter().all(char::is_ascii_digit);
+        match part.len() {
+            4 => digits && self.options.lone_years,
+            6 | 8 => digits && self.options.split_compact_dates,
+            _ => false,
+        }
+    }
+    fn take_holder(&mut self) -> Option<Token> {
+        let date_holder = std::mem::replace(&mut self.date_holder, DateHolder::new());
+        let mut separators = std::mem::take(&mut self.separators);
+        separators.truncate(date_holder.len().saturating_sub(1));
+        let dotted = std::mem::take(&mut self.dotted);
+        let qualifier = self.qualifier.take();
+        // a compact run skipped for not being a date
+        if date_holder.is_empty() {
+            return None;
+        }
+        if dotted
+            && self.options.dot_policy == DotPolicy::RequireYearContext
+            && !date_holder.holding.iter().any(|part| part.len() == 4)
+        {
+            self.record(TraceEvent::HolderSkipped(date_holder.to_string()));
+            return None;
+        }
+        if self.options.skip_thousands_groups && is_thousands_group(&date_holder, &separators) {
+            self.record(TraceEvent::HolderSkipped(date_holder.to_string()));
+            return None;
+        }
+        self.record(TraceEvent::HolderPushed(date_holder.to_string()));
+        Some(Token {
+            date_holder,
+            separators,
+            qualifier,
+            span: self.holder_span.clone(),
+        })
+    }
+}
+
+/// A decision made while scanning, as reported by [`scan_trace`]. Parts and candidates are
+/// shown with their parts joined by spaces.
+#[derive(Debug, PartialEq)]
+pub enum TraceEvent {
+    /// A part began a new candidate date.
+    TokenStarted,
+    /// A run of digits or a month name was added to the candidate.
+    PartAdded(String),
+    /// The candidate was dropped for having fewer than two parts.
+    HolderTruncated(String),
+    /// The candidate was handed on to be resolved.
+    HolderPushed(String),
+    /// The candidate was dropped by the dot policy or as a thousands group.
+    HolderSkipped(String),
+    /// What the last pushed candidate resolved to.
+    Resolved(Result<Date, DateError>),
+}
+
+/// Scans `s` as [`find_dates`] does, returning each decision the scanner made along the way.
+pub fn scan_trace(s: &str) -> Vec<TraceEvent> {
+    let options = ParseOptions::default();
+    let mut scanner = Scanner::new(&options);
+    scanner.trace = Some(vec![]);
+    let mut events = vec![];
+    let mut resolve = |scanner: &mut Scanner, token: Option<Token>| {
+        events.append(scanner.trace.as_mut().unwrap());
+        if let Some(token) = token {
+            events.push(TraceEvent::Resolved(token.as_date_with(&options)));
+        }
+    };
+    for letter in s.chars() {
+        let token = scanner.push_char(letter);
+        resolve(&mut scanner, token);
+    }
+    let token = scanner.finish();
+    resolve(&mut scanner, token);
+    events
+}
+
+/// Finds dates in input that arrives in pieces, such as lines typed into a REPL. A date split
+/// across chunks is held back until a later chunk (or [`IncrementalParser::finish`]) shows
+/// where it ends. `learn_order` has no effect, since it needs the whole input.
+pub struct IncrementalParser {
+    scanner: Scanner<'static>,
+    // the start of a UTF-8 sequence cut off by the end of a byte chunk
+    pending: Vec<u8>,
+}
+
+impl IncrementalParser {
+    pub fn new() -> Self {
+        Self::with_options(ParseOptions::default())
+    }
+    pub fn with_options(options: ParseOptions) -> Self {
+        Self {
+            scanner: Scanner::with_options(Cow::Owned(options)),
+            pending: vec![],
+        }
+    }
+    /// Scans `chunk`, returning the dates it completed.
+    pub fn feed(&mut self, chunk: &str) -> Vec<Result<Date, DateError>> {
+        without_spans(self.feed_with_spans(chunk))
+    }
+    /// Like [`IncrementalParser::feed`], also returning where each date was found as a byte
+    /// range of the whole stream fed so far.
+    pub fn feed_with_spans(&mut self, chunk: &str) -> Vec<(Result<Date, DateError>, Range<usize>)> {
+        let mut dates = vec![];
+        for letter in chunk.chars() {
+            self.push(letter, letter.len_utf8(), &mut dates);
+        }
+        dates
+    }
+    /// Like [`IncrementalParser::feed_with_spans`] for raw bytes, such as reads from a socket,
+    /// where a multi-byte character may be split between chunks. Invalid UTF-8 is read as
+    /// U+FFFD, which ends a date like any other non-date character.
+    pub fn feed_bytes_with_spans(
+        &mut self,
+        chunk: &[u8],
+    ) -> Vec<(Result<Date, DateError>, Range<usize>)> {
+        self.pending.extend_from_slice(chunk);
+        let pending = std::mem::take(&mut self.pending);
+        let mut dates = vec![];
+        let mut rest = &pending[..];
+        loop {
+            match std::str::from_utf8(rest) {
+                Ok(valid) => {
+                    dates.extend(self.feed_with_spans(valid));
+                    break;
+                }
+                Err(err) => {
+                    let (valid, invalid) = rest.split_at(err.valid_up_to());
+                    // from_utf8 vouched for the bytes before the error
+                    dates.extend(self.feed_with_spans(std::str::from_utf8(valid).unwrap()));
+                    let Some(len) = err.error_len() else {
+                        self.pending = invalid.to_vec();
+                        break;
+                    };
+                    self.push(char::REPLACEMENT_CHARACTER, len, &mut dates);
+                    rest = &invalid[len..];
+                }
+            }
+        }
+        dates
+    }
+    fn push(
+        &mut self,
+        letter: char,
+        width: usize,
+        dates: &mut Vec<(Result<Date, DateError>, Range<usize>)>,
+    ) {
+        if let Some(token) = self.scanner.push_sized(letter, width) {
+            dates.push((token.as_date_with(&self.scanner.options), token.span));
+        }
+    }
+    /// Ends the input, returning a date that was still in progress.
+    pub fn finish(self) -> Vec<Result<Date, DateError>> {
+        without_spans(self.finish_with_spans())
+    }
+    /// Like [`IncrementalParser::finish`], also returning where the date was found.
+    pub fn finish_with_spans(mut self) -> Vec<(Result<Date, DateError>, Range<usize>)> {
+        let mut dates = vec![];
+        // a sequence still cut off can't be completed any more
+        if !self.pending.is_empty() {
+            let width = self.pending.len();
+            self.push(char::REPLACEMENT_CHARACTER, width, &mut dates);
+        }
+        if let Some(token) = self.scanner.finish() {
+            dates.push((token.as_date_with(&self.scanner.options), token.span));
+        }
+        dates
+    }
+}
+
+fn without_spans(
+    dates: Vec<(Result<Date, DateError>, Range<usize>)>,
+) -> Vec<Result<Date, DateError>> {
+    dates.into_iter().map(|(date, _)| date).collect()
+}
+
+impl Default for IncrementalParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn find_first_date(s: &str) -> Result<Date, DateError> {
+    match find_dates_limited(s, 1).pop() {
+        Some(date_result) => date_result,
+        None => Err(DateError::NoDatesFound(s.to_string())),
+    }
+}
+
+pub fn find_last_date(s: &str) -> Result<Date, DateError> {
+    match find_dates(s).pop() {
+        Some(date_result) => date_result,
+        None => Err(DateError::NoDatesFound(s.to_string())),
+    }
+}
+
+const MONTH_NAMES: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+// full english month names or their three letter abbreviations, case-insensitively
+fn month_from_name(name: &str) -> Option<u16> {
+    let name = name.to_ascii_lowercase();
+    MONTH_NAMES
+        .iter()
+        .position(|month| *month == name || (name.len() == 3 && month.starts_with(&name)))
+        .map(|i| i as u16 + 1)
+}
+
+/// One element of a layout understood by [`parse_with_format_tokens`].
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum FormatToken {
+    /// A year of exactly `digits` digits. Two digit years are expanded around the default
+    /// [`ParseOptions::two_digit_year_pivot`], so `99` is 1999 and `23` is 2023.
+    Year { digits: u8 },
+    /// A numeric month of one or two digits.
+    Month,
+    /// A numeric day of one or two digits.
+    Day,
+    /// An English month name or its three letter abbreviation, in any case.
+    MonthName,
+    /// A character that must appear as is.
+    Literal(char),
+}
+
+/// Parses `s` as exactly the layout described by `tokens`, e.g. `[Day, Literal('-'), MonthName,
+/// Literal('-'), Year { digits: 4 }]` for `05-Oct-2023`.
+pub fn parse_with_format_tokens(s: &str, tokens: &[FormatToken]) -> Result<Date, DateError> {
+    let mismatch = || DateError::InvalidDateFormat(s.to_string());
+    let mut date = Date::new();
+    let mut rest = s;
+    for token in tokens {
+        match token {
+            FormatToken::Year { digits } => {
+                let (year, tail) =
+                    take_digits(rest, *digits as usize, *digits as usize).ok_or_else(mismatch)?;
+                date.year = Some(match digits {
+                    2 => expand_year(year, DEFAULT_YEAR_PIVOT),
+                    _ => year,
+                });
+                rest = tail;
+            }
+            FormatToken::Month => {
+                let (month, tail) = take_digits(rest, 1, 2).ok_or_else(mismatch)?;
+                date.month = Some(month);
+                rest = tail;
+            }
+            FormatToken::Day => {
+                let (day, tail) = take_digits(rest, 1, 2).ok_or_else(mismatch)?;
+                date.day = Some(day);
+                rest = tail;
+            }
+            FormatToken::MonthName => {
+                let len = rest.chars().take_while(char::is_ascii_alphabetic).count();
+                date.month = Some(month_from_name(&rest[..len]).ok_or_else(mismatch)?);
+                rest = &rest[len..];
+            }
+            FormatToken::Literal(ch) => rest = rest.strip_prefix(*ch).ok_or_else(mismatch)?,
+        }
+    }
+    if !rest.is_empty() {
+        return Err(mismatch());
+    }
+    date.validate()?;
+    Ok(date)
+}
+
+/// Parses `s` against a strftime style `format` built from `%Y` (4-digit year), `%y` (2-digit
+/// year, 69 to 99 in the 1900s and the rest in the 2000s, as the scanner reads them), `%m`,
+/// `%d`, `%b`/`%B` (month name) and `%%`; any other character must match itself.
+pub fn parse_exact(s: &str, format: &str) -> Result<Date, DateError> {
+    let mut tokens = vec![];
+    let mut chars = format.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '%' {
+            tokens.push(FormatToken::Literal(ch));
+            continue;
+        }
+        tokens.push(match chars.next() {
+            Some('Y') => FormatToken::Year { digits: 4 },
+            Some('y') => FormatToken::Year { digits: 2 },
+            Some('m') => FormatToken::Month,
+            Some('d') => FormatToken::Day,
+            Some('b' | 'B') => FormatToken::MonthName,
+            Some('%') => FormatToken::Literal('%'),
+            _ => return Err(DateError::InvalidDateFormat(format.to_string())),
+        });
+    }
+    parse_with_format_tokens(s, &tokens)
+}
+
+/// Tries each of `formats` in turn with [`parse_exact`], returning the first date that parses
+/// or every failure in order.
+pub fn parse_any(s: &str, formats: &[&str]) -> Result<Date, DateError> {
+    let mut errors = vec![];
+    for format in formats {
+        match parse_exact(s, format) {
+            Ok(date) => return Ok(date),
+            Err(err) => errors.push(err),
+        }
+    }
+    Err(DateError::NoFormatMatched(errors))
+}
+
+/// Parses `s`, once trimmed, as exactly one date, failing rather than guessing: with
+/// `NoDatesFound` when it's empty, `InvalidDateFormat` when there is anything besides the date
+/// and `UndecidedDate` when the date's order can't be told. Otherwise as
+/// [`FromStr`](std::str::FromStr), taking a `&str` for passing to combinators such as `map`.
+pub fn parse_one(s: &str) -> Result<Date, DateError> {
+    let trimmed = s.trim();
+    if trimmed.is_empty() {
+        return Err(DateError::NoDatesFound(s.to_string()));
+    }
+    trimmed.parse()
+}
+
+const DEFAULT_YEAR_PIVOT: u16 = 69;
+
+// a two digit year in the century `pivot` puts it in
+fn expand_year(value: u16, pivot: u16) -> u16 {
+    value + if value >= pivot { 1900 } else { 2000 }
+}
+
+const NULL_MARKERS: [&str; 6] = ["", "-", "n/a", "na", "none", "null"];
+
+/// Parses a table cell holding one date or a marker for no date: `N/A`, `NA`, `None`, `null`,
+/// `-` or nothing, in any case and with surrounding whitespace. Anything else must parse as a
+/// date with [`FromStr`](std::str::FromStr).
+pub fn parse_optional_date(s: &str) -> Result<Option<Date>, DateError> {
+    let s = s.trim();
+    if NULL_MARKERS
+        .iter()
+        .any(|marker| s.eq_ignore_ascii_case(marker))
+    {
+        return Ok(None);
+    }
+    s.parse().map(Some)
+}
+
+fn take_digits(s: &str, min: usize, max: usize) -> Option<(u16, &str)> {
+    let len = s.bytes().take(max).take_while(u8::is_ascii_digit).count();
+    if len < min {
+        return None;
+    }
+    Some((s[..len].parse().ok()?, &s[len..]))
+}
+
+#[derive(Clone, Debug)]
+struct Part(Vec<char>);
+
+impl Part {
+    fn new() -> Self {
+        Self(vec![])
+    }
+    fn truncate(&mut self) {
+        self.0.truncate(0);
+    }
+    fn push(&mut self, ch: char) {
+        self.0.push(ch);
+    }
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+    // the week of an ISO 8601 week date, written `W40`
+    fn week(&self) -> Option<Result<u16, DateError>> {
+        match self.0.split_first() {
+            Some(('w', digits)) => Some(Part(digits.to_vec()).to_u16()),
+            _ => None,
+        }
+    }
+    // the month a month name part stands for
+    fn month(&self) -> Option<u16> {
+        match self.0.first() {
+            Some(ch) if ch.is_alphabetic() => month_from_name(&self.to_string()),
+            _ => None,
+        }
+    }
+    // eight digits, as in `20231005`
+    fn is_compact_date(&self) -> bool {
+        self.len() == 8 && self.0.iter().all(char::is_ascii_digit)
+    }
+    fn is_epoch(&self) -> bool {
+        self.0.first() == Some(&'@')
+    }
+    fn to_epoch_date(&self) -> Result<Date, DateError> {
+        let seconds = self.0[1..].iter().collect::<String>().parse::<i64>()?;
+        Date::from_day_number(seconds.div_euclid(86400))
+            .map_err(|_| DateError::InvalidDateFormat(self.to_string()))
+    }
+    fn to_u16(&self) -> Result<u16, DateError> {
+        let digits = self.to_string();
+        // a part of only zeros leaves nothing after its leading zeros, but is still zero
+        let significant = match digits.trim_start_matches('0') {
+            "" if !digits.is_empty() => "0",
+            rest => rest,
+        };
+        Ok(significant.parse::<u16>()?)
+    }
+    // the part as a year, with two digit years expanded around the pivot
+    fn to_year(&self, options: &ParseOptions) -> Result<u16, DateError> {
+        let value = self.to_u16()?;
+        Ok(match options.two_digit_year_pivot {
+            Some(pivot) if self.len() == 2 => expand_year(value, pivot),
+            _ => value,
+        })
+    }
+}
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for ch in self.0.iter() {
+            write!(f, "{ch}")?;
+        }
+        Ok(())
+    }
+}
+#[derive(Clone, Debug)]
+struct DateHolder {
+    holding: Vec<Part>,
+}
+impl Display for DateHolder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut str = String::new();
+        for part in self.holding.iter() {
+            str.push_str((part.to_string() + " ").as_str());
+        }
+        str.pop(); // remove last space
+        write!(f, "{}", str)
+    }
+}
+impl DateHolder {
+    fn new() -> Self {
+        Self { holding: vec![] }
+    }
+    fn add_date_part(&mut self, part: &mut Part) {
+        self.holding.push(part.clone());
+        part.truncate();
+    }
+    fn truncate(&mut self) {
+        self.holding.truncate(0);
+    }
+    fn is_empty(&self) -> bool {
+        self.holding.is_empty()
+    }
+    fn len(&self) -> usize {
+        self.holding.len()
+    }
+    fn as_date(&self) -> Result<Date, DateError> {
+        self.as_date_with(&ParseOptions::default())
+    }
+    fn as_date_with(&self, options: &ParseOptions) -> Result<Date, DateError> {
+        self.as_date_ordered(options, options.order)
+    }
+    fn as_date_ordered(
+        &self,
+        options: &ParseOptions,
+        order: Option<DateOrder>,
+    ) -> Result<Date, DateError> {
+        if let Some(position) = self.holding.iter().position(|part| part.month().is_some()) {
+            // with three numbers besides it the month name is just a word, as in
+            // `we may 2023-10-05`
+            if self.holding.len() > 3 {
+                let mut holding = self.holding.clone();
+                holding.remove(position);
+                return DateHolder { holding }.as_date_ordered(options, order);
+            }
+            return self.as_named_date(position, options)?.checked(options);
+        }
+        if let Some(week) = self.holding.get(1).and_then(Part::week) {
+            return match &self.holding[..] {
+                [year, _, weekday] => Date::from_iso_week(year.to_u16()?, week?, weekday.to_u16()?),
+                _ => Err(DateError::InvalidDateFormat(self.to_string())),
+            };
+        }
+        if let [first, second, rest @ ..] = &self.holding[..]
+            && first.len() == 4
+            && first.0 == second.0
+        {
+            if !options.collapse_duplicate_year {
+                return Err(DateError::DuplicateComponent(first.to_u16()?));
+            }
+            let mut holding = vec![first.clone()];
+            holding.extend_from_slice(rest);
+            return DateHolder { holding }.as_date_ordered(options, order);
+        }
+        if let Some(order) = order
+            && self.holding.len() == 3
+            && let Some(date) = self.as_ordered_date(order, options)?
+        {
+            return date.checked(options);
+        }
+        if let (Some(order), Some(year)) = (order, options.assume_year)
+            && self.holding.len() == 2
+            && let Some(date) = self.as_month_day(order, year)?
+        {
+            return date.checked(options);
+        }
+        let mut year = None;
+        let mut month = None;
+        let mut day = None;
+        match self.holding.len() {
+            1 if self.holding[0].is_epoch() => return self.holding[0].to_epoch_date(),
+            1 if options.lone_years
+                && self.holding[0].len() == 4
+                && self.holding[0].0.iter().all(char::is_ascii_digit) =>
+            {
+                year = Some(self.holding[0].to_u16()?);
+            }
+            2 => {
+                let opt1 = self.holding[0].to_u16()?;
+                let opt2 = self.holding[1].to_u16()?;
+                // a written out year anchors the order, so `13-2023` has month 13 and
+                // `0005-10` is year 5
+                if self.holding[1].len() > 2 && self.holding[0].len() <= 2 {
+                    month = Some(opt1);
+                    year = Some(opt2);
+                } else if self.holding[0].len() > 2 && self.holding[1].len() <= 2 {
+                    year = Some(opt1);
+                    month = Some(opt2);
+                } else if opt1 > 12 {
+                    year = Some(self.holding[0].to_year(options)?);
+                    month = Some(opt2);
+                } else if opt2 > 12 {
+                    month = Some(opt1);
+                    year = Some(self.holding[1].to_year(options)?);
+                } else {
+                    return Err(DateError::UndecidedDate((Some(opt1), Some(opt2), None)));
+                }
+            }
+            3 => {
+                let opt1 = self.holding[0].to_u16()?;
+                let opt2 = self.holding[1].to_u16()?;
+                let opt3 = self.holding[2].to_u16()?;
+                let year_first = self.holding[0].len() > 2 || opt1 > 31;
+                let year_last = self.holding[2].len() > 2 || opt3 > 31;
+                // if first date is a year, or greater than 12 with no year last, it's year
+                if year_first || (opt1 > 12 && !year_last) {
+                    year.replace(self.holding[0].to_year(options)?);
+                    month.replace(opt2);
+                    day.replace(opt3);
+                    // if last date is a year and first is greater than 12, first is day
+                } else if year_last && opt1 > 12 {
+                    day.replace(opt1);
+                    month.replace(opt2);
+                    year.replace(self.holding[2].to_year(options)?);
+                    // if middle date is greater than 12, it's day
+                } else if opt2 > 12 {
+                    month.replace(opt1);
+                    day.replace(opt2);
+                    year.replace(self.holding[2].to_year(options)?);
+                    // if all dates are equal it doesnt matter
+                } else if opt1 == opt2 && opt2 == opt3 {
+                    year.replace(self.holding[0].to_year(options)?);
+                    month.replace(opt2);
+                    day.replace(opt3);
+                    // a zero month or day is invalid whichever way round they go
+                } else if opt1 == 0 || opt2 == 0 {
+                    let field = if opt1 == 0 {
+                        DateField::Month
+                    } else {
+                        DateField::Day
+                    };
+                    return Err(DateError::InvalidCalendarDate(field, 0));
+                    // otherwise undecided
+                } else {
+                    return Err(DateError::UndecidedDate((
+                        Some(opt1),
+                        Some(opt2),
+                        Some(opt3),
+                    )));
+                }
+            }
+            _ => return Err(DateError::InvalidDateFormat(self.to_string())),
+        }
+        Date { year, month, day }.checked(options)
+    }
+    // with the month spelled out, the numbers left are a day and a year, the year being written
+    // with more than two digits or above 31. Failing that, the year goes last, as in
+    // `Oct 05 23` and `05 Oct 23`, unless the month name does.
+    fn as_named_date(&self, position: usize, options: &ParseOptions) -> Result<Date, DateError> {
+        let month = self.holding[position].month();
+        let numbers: Vec<&Part> = self
+            .holding
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != position)
+            .map(|(_, part)| part)
+            .collect();
+        let is_year = |part: &Part, value: u16| part.len() > 2 || value > 31;
+        match numbers[..] {
+            [part] => {
+                let value = part.to_u16()?;
+                if is_year(part, value) {
+                    Ok(Date {
+                        year: Some(part.to_year(options)?),
+                        month,
+                        day: None,
+                    })
+                } else if let Some(year) = options.assume_year {
+                    Ok(Date {
+                        year: Some(year),
+                        month,
+                        day: Some(value),
+                    })
+                } else {
+                    Err(DateError::IncompleteDate((None, month, Some(value))))
+                }
+            }
+            [first, second] => {
+                let (opt1, opt2) = (first.to_u16()?, second.to_u16()?);
+                let (year, day) = match (is_year(first, opt1), is_year(second, opt2)) {
+                    (true, true) => return Err(DateError::InvalidDateFormat(self.to_string())),
+                    (true, false) => (first, opt2),
+                    (false, true) => (second, opt1),
+                    (false, false) if position == 2 => (first, opt2),
+                    (false, false) => (second, opt1),
+                };
+                Ok(Date {
+                    year: Some(year.to_year(options)?),
+                    month,
+                    day: Some(day),
+                })
+            }
+            _ => Err(DateError::InvalidDateFormat(self.to_string())),
+        }
+    }
+    // the order a three part date gives away on its own, through a 4-digit or > 31 year and a
+    // day > 12
+    fn implied_order(&self) -> Option<DateOrder> {
+        if self.holding.len() != 3 {
+            return None;
+        }
+        let opt1 = self.holding[0].to_u16().ok()?;
+        let opt2 = self.holding[1].to_u16().ok()?;
+        let opt3 = self.holding[2].to_u16().ok()?;
+        if self.holding[0].len() > 2 || opt1 > 31 {
+            Some(DateOrder::Ymd)
+        } else if self.holding[2].len() > 2 || opt3 > 31 {
+            if opt1 > 12 && opt2 <= 12 {
+                Some(DateOrder::Dmy)
+            } else if opt2 > 12 && opt1 <= 12 {
+                Some(DateOrder::Mdy)
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+    // two parts without a written year are a month and day in the hinted order
+    fn as_month_day(&self, order: DateOrder, year: u16) -> Result<Option<Date>, DateError> {
+        let opt1 = self.holding[0].to_u16()?;
+        let opt2 = self.holding[1].to_u16()?;
+        if self.holding.iter().any(|part| part.len() > 2) || opt1 > 31 || opt2 > 31 {
+            return Ok(None);
+        }
+        let (month, day) = match order {
+            DateOrder::Dmy => (opt2, opt1),
+            DateOrder::Ymd | DateOrder::Mdy => (opt1, opt2),
+        };
+        Ok(Some(Date {
+            year: Some(year),
+            month: Some(month),
+            day: Some(day),
+        }))
+    }
+    // the hint only applies when it agrees with where a year is clearly written
+    fn as_ordered_date(
+        &self,
+        order: DateOrder,
+        options: &ParseOptions,
+    ) -> Result<Option<Date>, DateError> {
+        let opt1 = self.holding[0].to_u16()?;
+        let opt2 = self.holding[1].to_u16()?;
+        let opt3 = self.holding[2].to_u16()?;
+        let year_first = self.holding[0].len() > 2 || opt1 > 31;
+        let year_last = self.holding[2].len() > 2 || opt3 > 31;
+        let (year, month, day) = match order {
+            _ if year_first => (&self.holding[0], opt2, opt3),
+            DateOrder::Ymd if year_last => return Ok(None),
+            DateOrder::Ymd => (&self.holding[0], opt2, opt3),
+            DateOrder::Dmy => (&self.holding[2], opt2, opt1),
+            DateOrder::Mdy => (&self.holding[2], opt1, opt2),
+        };
+        Ok(Some(Date {
+            year: Some(year.to_year(options)?),
+            month: Some(month),
+            day: Some(day),
+        }))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DateError {
+    NoDatesFound(String),
+    UndecidedDate((Option<u16>, Option<u16>, Option<u16>)),
+    InvalidDateFormat(String),
+    InvalidCalendarDate(DateField, u16),
+    IncompleteDate((Option<u16>, Option<u16>, Option<u16>)),
+    YearOutOfRange(i64),
+    DuplicateComponent(u16),
+    OrphanedComponent(DateField),
+    NoFormatMatched(Vec<DateError>),
+    FormatMismatch(String),
+    InvalidTime((u8, u8, u8)),
+    ParseIntError(ParseIntError),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DateField {
+    Year,
+    Month,
+    Day,
+    Week,
+    Weekday,
+}
+
+impl Display for DateField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DateField::Year => write!(f, "year"),
+            DateField::Month => write!(f, "month"),
+            DateField::Day => write!(f, "day"),
+            DateField::Week => write!(f, "week"),
+            DateField::Weekday => write!(f, "weekday"),
+        }
+    }
+}
+
+impl Display for DateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DateError::NoDatesFound(msg) => write!(f, "No dates found from {}", msg),
+            DateError::UndecidedDate(msg) => write!(
+                f,
+                "unable to determine date from values: {:?} {:?} {:?}",
+                msg.0, msg.1, msg.2
+            ),
+            DateError::InvalidDateFormat(msg) => write!(f, "Invalid date format from {}", msg),
+            DateError::InvalidCalendarDate(field, value) => {
+                write!(f, "Invalid calendar date, {field} {value} is out of range")
+            }
+            DateError::IncompleteDate(msg) => write!(
+                f,
+                "Incomplete date from values: {:?} {:?} {:?}",
+                msg.0, msg.1, msg.2
+            ),
+            DateError::YearOutOfRange(year) => write!(f, "Year {year} can't be represented"),
+            DateError::DuplicateComponent(value) => write!(f, "Year {value} is written twice"),
+            DateError::OrphanedComponent(field) => {
+                write!(
+                    f,
+                    "A {field} can't be given without the components above it"
+                )
+            }
+            DateError::NoFormatMatched(errors) => {
+                write!(f, "No format matched")?;
+                for (i, err) in errors.iter().enumerate() {
+                    write!(f, "{} {err}", if i == 0 { ":" } else { ";" })?;
+                }
+                Ok(())
+            }
+            DateError::FormatMismatch(s) => write!(f, "{s} is not laid out as YYYY-MM-DD"),
+            DateError::InvalidTime((hour, minute, second)) => {
+                write!(f, "Invalid time {hour:02}:{minute:02}:{second:02}")
+            }
+            DateError::ParseIntError(err) => write!(f, "{err}",),
+        }
+    }
+}
+
+impl From<ParseIntError> for DateError {
     fn from(err: ParseIntError) -> Self {
         DateError::InvalidDateFormat(err.to_string())
     }
-}
+}
+
+impl std::error::Error for DateError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ymd(year: u16, month: u16, day: u16) -> Date {
+        Date {
+            year: Some(year),
+            month: Some(month),
+            day: Some(day),
+        }
+    }
+
+    #[test]
+    fn part_to_u16_check_1() {
+        let part = Part(vec!['2', '0', '2', '3']);
+        let num = part.to_u16().unwrap();
+        assert_eq!(num, 2023);
+    }
+    #[test]
+    fn part_to_u16_check_2() {
+        let part = Part(vec!['0', '5']);
+        let num = part.to_u16().unwrap();
+        assert_eq!(num, 5);
+    }
+    #[test]
+    fn part_to_u16_check_3() {
+        let part = |s: &str| Part(s.chars().collect());
+        assert_eq!(part("007").to_u16(), Ok(7));
+        assert_eq!(part("0000").to_u16(), Ok(0));
+        assert_eq!(part("0123").to_u16(), Ok(123));
+        assert_eq!(part("0").to_u16(), Ok(0));
+        assert!(part("").to_u16().is_err());
+        assert!(part("00065536").to_u16().is_err());
+    }
+
+    #[test]
+    fn date_holders_check_1() {
+        let dates = find_dates("2023-10-05");
+        let expected = vec![Ok(Date {
+            year: Some(2023),
+            month: Some(10),
+            day: Some(5),
+        })];
+        assert_eq!(dates, expected);
+    }
+    #[test]
+    fn date_holders_check_2() {
+        let dates = find_dates("2023-10-05 some other/random text 2021-11_21");
+        let expected = vec![
+            Ok(Date {
+                year: Some(2023),
+                month: Some(10),
+                day: Some(5),
+            }),
+            Ok(Date {
+                year: Some(2021),
+                month: Some(11),
+                day: Some(21),
+            }),
+        ];
+        assert_eq!(dates, expected);
+    }
+    #[test]
+    fn date_holders_check_3() {
+        let dates = find_dates("100 some random text 2023-10-05 some other/random text 2021-11_21");
+        let expected = vec![
+            Ok(Date {
+                year: Some(2023),
+                month: Some(10),
+                day: Some(5),
+            }),
+            Ok(Date {
+                year: Some(2021),
+                month: Some(11),
+                day: Some(21),
+            }),
+        ];
+        assert_eq!(dates, expected);
+    }
+
+    #[test]
+    fn cast_date_holder_to_date_1() {
+        let date = DateHolder {
+            holding: vec![
+                Part(vec!['2', '0', '2', '3']),
+                Part(vec!['1', '0']),
+                Part(vec!['0', '5']),
+            ],
+        }
+        .as_date()
+        .unwrap();
+        assert_eq!(date, Date {
+            year: Some(2023),
+            month: Some(10),
+            day: Some(5),
+        });
+    }
+    #[test]
+    fn cast_date_holder_to_date_2() {
+        let date = DateHolder {
+            holding: vec![
+                Part(vec!['1', '2']),
+                Part(vec!['1', '0']),
+                Part(vec!['0', '5']),
+            ],
+        }
+        .as_date();
+        assert_eq!(
+            date,
+            Err(DateError::UndecidedDate((Some(12), Some(10), Some(5))))
+        );
+    }
+
+    #[test]
+    fn cmp_dates() {
+        let date1 = Date {
+            year: Some(2023),
+            month: Some(10),
+            day: Some(5),
+        };
+        let date2 = Date {
+            year: Some(2022),
+            month: Some(12),
+            day: Some(31),
+        };
+        assert!(date1 > date2);
+        let date3 = Date {
+            year: Some(2023),
+            month: None,
+            day: None,
+        };
+        assert!(date3 < date1);
+        let date4 = Date {
+            year: Some(2023),
+            month: Some(10),
+            day: None,
+        };
+        assert!(date3 < date4);
+    }
+
+    #[test]
+    fn invalid_calendar_dates() {
+        assert_eq!(find_dates("99-99-99"), vec![Err(
+            DateError::InvalidCalendarDate(DateField::Month, 99)
+        )]);
+        assert_eq!(find_dates("2023-13-01"), vec![Err(
+            DateError::InvalidCalendarDate(DateField::Month, 13)
+        )]);
+        assert_eq!(find_dates("2023-02-29"), vec![Err(
+            DateError::InvalidCalendarDate(DateField::Day, 29)
+        )]);
+    }
+
+    #[test]
+    fn find_dates_with_diagnostics_check() {
+        let (dates, diagnostics) =
+            find_dates_with_diagnostics("99-99-99 then 2023-10-05 and 2023-13-01");
+        assert_eq!(dates, vec![Ok(Date {
+            year: Some(2023),
+            month: Some(10),
+            day: Some(5),
+        })]);
+        assert_eq!(diagnostics, vec![
+            Diagnostic {
+                token: "99-99-99".to_string(),
+                error: DateError::InvalidCalendarDate(DateField::Month, 99),
+            },
+            Diagnostic {
+                token: "2023-13-01".to_string(),
+                error: DateError::InvalidCalendarDate(DateField::Month, 13),
+            },
+        ]);
+        let (_, diagnostics) = find_dates_with_diagnostics("due Feb 30 2023.");
+        assert_eq!(diagnostics[0].token, "Feb 30 2023");
+    }
+
+    #[test]
+    fn humanize_since_check() {
+        let reference = Date {
+            year: Some(2023),
+            month: Some(10),
+            day: Some(5),
+        };
+        let offset = |year, month, day| ymd(year, month, day).humanize_since(&reference);
+        assert_eq!(offset(2023, 10, 5), Some("today".to_string()));
+        assert_eq!(offset(2023, 10, 2), Some("3 days ago".to_string()));
+        assert_eq!(offset(2023, 10, 6), Some("in 1 day".to_string()));
+        assert_eq!(offset(2023, 10, 19), Some("in 2 weeks".to_string()));
+        assert_eq!(offset(2023, 8, 1), Some("2 months ago".to_string()));
+        assert_eq!(offset(2022, 10, 5), Some("1 year ago".to_string()));
+        let partial = Date {
+            year: Some(2023),
+            month: Some(10),
+            day: None,
+        };
+        assert_eq!(partial.humanize_since(&reference), None);
+        assert_eq!(offset(2023, 2, 30), None);
+        assert_eq!(reference.humanize_since(&reference.with_day(40)), None);
+    }
+
+    #[test]
+    fn epoch_seconds() {
+        assert_eq!(find_dates("@1696500000"), vec![Ok(ymd(2023, 10, 5))]);
+        assert_eq!(find_dates("touched @1696500000 by cron"), vec![Ok(ymd(
+            2023, 10, 5
+        ))]);
+        assert_eq!(find_dates("email@ 2023"), vec![]);
+        assert_eq!(find_dates("@0 and @99999999999999"), vec![
+            Ok(Date {
+                year: Some(1970),
+                month: Some(1),
+                day: Some(1),
+            }),
+            Err(DateError::InvalidDateFormat("@99999999999999".to_string())),
+        ]);
+    }
+
+    #[test]
+    fn find_valid_dates_check() {
+        let dates = find_valid_dates("2023-10-05 then 12-10-05 then 2023-13-01 and 2021/11/21");
+        assert_eq!(dates, vec![
+            Date {
+                year: Some(2023),
+                month: Some(10),
+                day: Some(5),
+            },
+            Date {
+                year: Some(2021),
+                month: Some(11),
+                day: Some(21),
+            },
+        ]);
+    }
+
+    #[test]
+    fn dot_policies() {
+        let with_policy = |s, dot_policy| {
+            find_dates_with(s, &ParseOptions {
+                dot_policy,
+                ..Default::default()
+            })
+        };
+        let undecided = || Err(DateError::UndecidedDate((Some(5), Some(10), Some(2023))));
+
+        let policy = DotPolicy::TreatAsSeparator;
+        assert_eq!(with_policy("3.14", policy), vec![Ok(Date {
+            year: Some(2014),
+            month: Some(3),
+            day: None,
+        })]);
+        assert_eq!(with_policy("05.10.2023", policy), vec![undecided()]);
+        assert_eq!(with_policy("1.2.3", policy), vec![Err(
+            DateError::UndecidedDate((Some(1), Some(2), Some(3)))
+        )]);
+
+        let policy = DotPolicy::RequireYearContext;
+        assert_eq!(with_policy("3.14", policy), vec![]);
+        assert_eq!(with_policy("05.10.2023", policy), vec![undecided()]);
+        assert_eq!(with_policy("1.2.3", policy), vec![]);
+
+        let policy = DotPolicy::Ignore;
+        assert_eq!(with_policy("3.14", policy), vec![]);
+        assert_eq!(with_policy("05.10.2023", policy), vec![]);
+        assert_eq!(with_policy("1.2.3", policy), vec![]);
+    }
+
+    #[test]
+    fn from_packed_check() {
+        assert_eq!(
+            Date::from_packed(20231005),
+            Ok(Date {
+                year: Some(2023),
+                month: Some(10),
+                day: Some(5),
+            })
+        );
+        assert_eq!(
+            Date::from_packed(20231345),
+            Err(DateError::InvalidCalendarDate(DateField::Month, 13))
+        );
+        assert_eq!(
+            Date::from_packed(999999999),
+            Err(DateError::InvalidDateFormat("999999999".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_with_format_tokens_check() {
+        use FormatToken::*;
+        let tokens = [Day, Literal('-'), MonthName, Literal('-'), Year {
+            digits: 4,
+        }];
+        assert_eq!(
+            parse_with_format_tokens("05-Oct-2023", &tokens),
+            Ok(ymd(2023, 10, 5))
+        );
+        assert_eq!(
+            parse_with_format_tokens("5-october-2023", &tokens),
+            Ok(ymd(2023, 10, 5))
+        );
+        assert_eq!(
+            parse_with_format_tokens("05-Okt-2023", &tokens),
+            Err(DateError::InvalidDateFormat("05-Okt-2023".to_string()))
+        );
+        assert_eq!(
+            parse_with_format_tokens("05-Oct-23", &tokens),
+            Err(DateError::InvalidDateFormat("05-Oct-23".to_string()))
+        );
+        assert_eq!(
+            parse_with_format_tokens("31-Apr-2023", &tokens),
+            Err(DateError::InvalidCalendarDate(DateField::Day, 31))
+        );
+    }
+
+    #[test]
+    fn find_dates_in_range_check() {
+        let dates = find_dates_in_range(
+            "2023-09-30 to 2023-10-01 to 2023-10 to 2023-10-15 to 2023-13-01 to 2023-10-31 to 2023-11-01",
+            Date {
+                year: Some(2023),
+                month: Some(10),
+                day: Some(1),
+            },
+            Date {
+                year: Some(2023),
+                month: Some(10),
+                day: Some(31),
+            },
+        );
+        let days = dates.iter().map(|date| date.day()).collect::<Vec<_>>();
+        assert_eq!(days, vec![Some(1), Some(15), Some(31)]);
+    }
+
+    #[test]
+    fn from_compact_str_check() {
+        assert_eq!(
+            Date::from_compact_str("20231005"),
+            Ok(Date {
+                year: Some(2023),
+                month: Some(10),
+                day: Some(5),
+            })
+        );
+        assert_eq!(
+            Date::from_compact_str("20231305"),
+            Err(DateError::InvalidCalendarDate(DateField::Month, 13))
+        );
+        for malformed in ["2023105", "202310050", "2023-105", "+2023105", ""] {
+            assert_eq!(
+                Date::from_compact_str(malformed),
+                Err(DateError::InvalidDateFormat(malformed.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn find_intervals_check() {
+        let intervals = find_intervals("stay 2023-10-05/2023-10-10 or 2023-10-05 2023-10-10");
+        assert_eq!(intervals, vec![(
+            Date {
+                year: Some(2023),
+                month: Some(10),
+                day: Some(5),
+            },
+            Date {
+                year: Some(2023),
+                month: Some(10),
+                day: Some(10),
+            }
+        )]);
+        assert_eq!(find_intervals("2023-10-05/2023-13-10"), vec![]);
+    }
+
+    #[test]
+    fn ordinal_and_days_remaining() {
+        assert_eq!(ymd(2023, 1, 1).ordinal(), Some(1));
+        assert_eq!(ymd(2023, 1, 1).days_remaining_in_year(), Some(364));
+        assert_eq!(ymd(2024, 1, 1).days_remaining_in_year(), Some(365));
+        assert_eq!(ymd(2023, 12, 31).ordinal(), Some(365));
+        assert_eq!(ymd(2023, 12, 31).days_remaining_in_year(), Some(0));
+        assert_eq!(ymd(2024, 12, 31).ordinal(), Some(366));
+        assert_eq!(ymd(2024, 12, 31).days_remaining_in_year(), Some(0));
+        let partial = Date {
+            year: Some(2023),
+            month: None,
+            day: None,
+        };
+        assert_eq!(partial.days_remaining_in_year(), None);
+    }
+
+    #[test]
+    fn parse_exact_check() {
+        assert_eq!(parse_exact("05-Oct-2023", "%d-%b-%Y"), Ok(ymd(2023, 10, 5)));
+        assert_eq!(parse_exact("2023/10/05", "%Y/%m/%d"), Ok(ymd(2023, 10, 5)));
+        assert_eq!(
+            parse_exact("100% 2023", "100%% %Y").unwrap().year(),
+            Some(2023)
+        );
+        assert_eq!(
+            parse_exact("2023", "%Q"),
+            Err(DateError::InvalidDateFormat("%Q".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_any_check() {
+        let date = parse_any("10/05/2023", &["%Y-%m-%d", "%m/%d/%Y", "%d/%m/%Y"]);
+        assert_eq!(
+            date,
+            Ok(Date {
+                year: Some(2023),
+                month: Some(10),
+                day: Some(5),
+            })
+        );
+        let err = parse_any("2023.10.05", &["%Y-%m-%d", "%Y/%m/%d"]).unwrap_err();
+        assert_eq!(
+            err,
+            DateError::NoFormatMatched(vec![
+                DateError::InvalidDateFormat("2023.10.05".to_string()),
+                DateError::InvalidDateFormat("2023.10.05".to_string()),
+            ])
+        );
+        assert_eq!(
+            err.to_string(),
+            "No format matched: Invalid date format from 2023.10.05; Invalid date format from 2023.10.05"
+        );
+    }
+
+    #[test]
+    fn clamp_year_check() {
+        let date = Date {
+            year: Some(5000),
+            month: Some(10),
+            day: Some(5),
+        };
+        assert_eq!(date.clamp_year(1900, 2100), Date {
+            year: Some(2100),
+            month: Some(10),
+            day: Some(5),
+        });
+        let date = Date {
+            year: Some(1066),
+            month: None,
+            day: None,
+        };
+        assert_eq!(date.clamp_year(1900, 2100).year(), Some(1900));
+        assert_eq!(Date::new().clamp_year(1900, 2100), Date::new());
+    }
+
+    #[test]
+    fn order_hint_and_auto_swap() {
+        let mdy = ParseOptions {
+            order: Some(DateOrder::Mdy),
+            ..Default::default()
+        };
+        assert_eq!(find_dates_with("13/05/2023", &mdy), vec![Err(
+            DateError::InvalidCalendarDate(DateField::Month, 13)
+        )]);
+        let swapping = ParseOptions {
+            auto_swap_on_invalid: true,
+            ..mdy.clone()
+        };
+        assert_eq!(find_dates_with("13/05/2023", &swapping), vec![Ok(ymd(
+            2023, 5, 13
+        ))]);
+        assert_eq!(find_dates_with("05/13/2023", &mdy), vec![Ok(ymd(
+            2023, 5, 13
+        ))]);
+        assert_eq!(find_dates_with("05/10/2023", &mdy), vec![Ok(Date {
+            year: Some(2023),
+            month: Some(5),
+            day: Some(10),
+        })]);
+        // a leading 4-digit year wins over the hint
+        assert_eq!(find_dates_with("2023-10-05", &mdy), vec![Ok(Date {
+            year: Some(2023),
+            month: Some(10),
+            day: Some(5),
+        })]);
+        assert_eq!(find_dates_with("13/40/2023", &swapping), vec![Err(
+            DateError::InvalidCalendarDate(DateField::Month, 13)
+        )]);
+    }
+
+    #[test]
+    fn to_rfc2822_date_check() {
+        assert_eq!(
+            ymd(2023, 10, 5).to_rfc2822_date(),
+            Some("Thu, 05 Oct 2023".to_string())
+        );
+        assert_eq!(
+            ymd(2000, 1, 1).to_rfc2822_date(),
+            Some("Sat, 01 Jan 2000".to_string())
+        );
+        assert_eq!(
+            ymd(1969, 12, 28).to_rfc2822_date(),
+            Some("Sun, 28 Dec 1969".to_string())
+        );
+        let partial = Date {
+            year: Some(2023),
+            month: Some(10),
+            day: None,
+        };
+        assert_eq!(partial.to_rfc2822_date(), None);
+        assert_eq!(partial.with_day(40).to_rfc2822_date(), None);
+    }
+
+    #[test]
+    fn find_unique_dates_by_check() {
+        let s = "2023-10-25 and 10/25/2023 and 2023-10 and 2023-10 and 2023-10-20 and 2023-11-01";
+        let dates = |precision| {
+            find_unique_dates_by(s, precision)
+                .iter()
+                .map(|date| (date.year(), date.month(), date.day()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(dates(DatePrecision::Day), vec![
+            (Some(2023), Some(10), Some(25)),
+            (Some(2023), Some(10), None),
+            (Some(2023), Some(10), Some(20)),
+            (Some(2023), Some(11), Some(1)),
+        ]);
+        assert_eq!(dates(DatePrecision::Month), vec![
+            (Some(2023), Some(10), Some(25)),
+            (Some(2023), Some(11), Some(1)),
+        ]);
+        assert_eq!(dates(DatePrecision::Year), vec![(
+            Some(2023),
+            Some(10),
+            Some(25)
+        )]);
+    }
+
+    #[test]
+    fn sub_dates() {
+        assert_eq!(ymd(2023, 11, 2) - ymd(2023, 10, 30), Ok(3));
+        assert_eq!(ymd(2023, 10, 30) - ymd(2023, 11, 2), Ok(-3));
+        assert_eq!(ymd(2024, 3, 1) - ymd(2024, 2, 28), Ok(2));
+        let partial = Date {
+            year: Some(2023),
+            month: Some(10),
+            day: None,
+        };
+        assert_eq!(
+            ymd(2023, 10, 5) - partial,
+            Err(DateError::IncompleteDate((Some(2023), Some(10), None)))
+        );
+        assert_eq!(
+            ymd(2023, 12, 40) - ymd(2023, 12, 1),
+            Err(DateError::InvalidCalendarDate(DateField::Day, 40))
+        );
+        assert_eq!(
+            ymd(2023, 12, 1) - ymd(2023, 2, 30),
+            Err(DateError::InvalidCalendarDate(DateField::Day, 30))
+        );
+    }
+
+    #[test]
+    fn add_and_sub_days() {
+        assert_eq!(ymd(2023, 10, 5) + Days(7), Ok(ymd(2023, 10, 12)));
+        assert_eq!(ymd(2023, 12, 28) + Days(7), Ok(ymd(2024, 1, 4)));
+        assert_eq!(ymd(2024, 3, 1) - Days(1), Ok(ymd(2024, 2, 29)));
+        assert_eq!(ymd(2023, 3, 1) + Days(-1), Ok(ymd(2023, 2, 28)));
+        assert_eq!(ymd(0, 1, 1) - Days(1), Err(DateError::YearOutOfRange(-1)));
+        assert_eq!(
+            Date::new() + Days(1),
+            Err(DateError::IncompleteDate((None, None, None)))
+        );
+        assert_eq!(
+            ymd(2023, 2, 30) + Days(1),
+            Err(DateError::InvalidCalendarDate(DateField::Day, 30))
+        );
+        assert_eq!(
+            ymd(2023, 2, 30) - Days(1),
+            Err(DateError::InvalidCalendarDate(DateField::Day, 30))
+        );
+    }
+
+    #[test]
+    fn assume_year_for_month_day() {
+        let options = |order| ParseOptions {
+            order: Some(order),
+            assume_year: Some(2023),
+            ..Default::default()
+        };
+        let date = |year, month, day| {
+            Ok(Date {
+                year: Some(year),
+                month: Some(month),
+                day,
+            })
+        };
+        assert_eq!(find_dates_with("10-05", &options(DateOrder::Mdy)), vec![
+            date(2023, 10, Some(5))
+        ]);
+        assert_eq!(find_dates_with("10-05", &options(DateOrder::Dmy)), vec![
+            date(2023, 5, Some(10))
+        ]);
+        assert_eq!(find_dates_with("2021-10", &options(DateOrder::Mdy)), vec![
+            date(2021, 10, None)
+        ]);
+        assert_eq!(
+            find_dates_with("10-05", &ParseOptions {
+                assume_year: Some(2023),
+                ..Default::default()
+            }),
+            vec![Err(DateError::UndecidedDate((Some(10), Some(5), None)))]
+        );
+    }
+
+    #[test]
+    fn any_valid_ordering_check() {
+        assert!(!any_valid_ordering(&[99, 99, 99]));
+        assert!(any_valid_ordering(&[5, 10, 2023]));
+        assert!(any_valid_ordering(&[2023, 31, 12]));
+        assert!(!any_valid_ordering(&[2023, 31, 40]));
+        assert!(any_valid_ordering(&[10, 2023]));
+        assert!(!any_valid_ordering(&[2023]));
+    }
+
+    #[test]
+    fn normalize_check() {
+        let orphaned_day = Date {
+            year: Some(2023),
+            month: None,
+            day: Some(5),
+        };
+        assert_eq!(orphaned_day.normalize(), Date {
+            year: Some(2023),
+            month: None,
+            day: None,
+        });
+        let no_year = Date {
+            year: None,
+            month: Some(10),
+            day: Some(5),
+        };
+        assert_eq!(no_year.normalize(), Date::new());
+        let full = Date {
+            year: Some(2023),
+            month: Some(10),
+            day: Some(5),
+        };
+        assert_eq!(full.normalize(), Date {
+            year: Some(2023),
+            month: Some(10),
+            day: Some(5),
+        });
+    }
+
+    #[test]
+    fn cmp_orphaned_components() {
+        let orphaned_day = Date {
+            year: Some(2023),
+            month: None,
+            day: Some(5),
+        };
+        let january = Date {
+            year: Some(2023),
+            month: Some(1),
+            day: None,
+        };
+        let year_only = Date {
+            year: Some(2023),
+            month: None,
+            day: None,
+        };
+        assert!(orphaned_day < january);
+        assert!(january > orphaned_day);
+        assert!(orphaned_day > year_only);
+        assert_ne!(orphaned_day, year_only);
+        assert_eq!(
+            orphaned_day.partial_cmp(&orphaned_day),
+            Some(std::cmp::Ordering::Equal)
+        );
+    }
+
+    #[test]
+    fn learn_order_from_unambiguous_dates() {
+        let s = "05-10-23 then 25-12-2023 then 31/01/2024";
+        let learning = ParseOptions {
+            learn_order: true,
+            ..Default::default()
+        };
+        assert_eq!(find_dates_with(s, &learning), vec![
+            Ok(Date {
+                year: Some(2023),
+                month: Some(10),
+                day: Some(5),
+            }),
+            Ok(Date {
+                year: Some(2023),
+                month: Some(12),
+                day: Some(25),
+            }),
+            Ok(Date {
+                year: Some(2024),
+                month: Some(1),
+                day: Some(31),
+            }),
+        ]);
+        assert_eq!(
+            find_dates(s)[0],
+            Err(DateError::UndecidedDate((Some(5), Some(10), Some(23))))
+        );
+        // a token's own evidence outweighs the majority
+        let s = "12/25/2023 then 25/12/2023 then 26/12/2023";
+        assert_eq!(
+            find_dates_with(s, &learning)[0].as_ref().unwrap().day(),
+            Some(25)
+        );
+    }
+
+    #[test]
+    fn signed_dates() {
+        let caesar = parse_era_year("44 BC").unwrap();
+        assert_eq!(caesar.era(), Era::Bce);
+        assert_eq!(caesar.signed_year(), Some(-44));
+        let charlemagne = parse_era_year("AD 800").unwrap();
+        assert_eq!(charlemagne.signed_year(), Some(800));
+        assert!(caesar < charlemagne);
+        assert!(parse_era_year("100 BCE").unwrap() < caesar);
+        assert!(parse_era_year("1 bce").unwrap() < parse_era_year("1 ce").unwrap());
+        let date = Date {
+            year: Some(2023),
+            month: Some(10),
+            day: Some(5),
+        };
+        assert_eq!(date.with_era(Era::Ce).unwrap().signed_year(), Some(2023));
+        let october = |era| {
+            Date {
+                year: None,
+                month: Some(10),
+                day: Some(5),
+            }
+            .with_era(era)
+            .unwrap()
+        };
+        assert_eq!(october(Era::Bce), october(Era::Ce));
+        assert_eq!(
+            october(Era::Bce).partial_cmp(&october(Era::Ce)),
+            Some(std::cmp::Ordering::Equal)
+        );
+        assert_eq!(
+            SignedDate::new(
+                Date {
+                    year: Some(0),
+                    ..date
+                },
+                Era::Ce
+            ),
+            Err(DateError::InvalidCalendarDate(DateField::Year, 0))
+        );
+        assert_eq!(
+            parse_era_year("0 BC"),
+            Err(DateError::InvalidCalendarDate(DateField::Year, 0))
+        );
+        assert_eq!(
+            parse_era_year("44 BC 2"),
+            Err(DateError::InvalidDateFormat("44 BC 2".to_string()))
+        );
+    }
+
+    #[test]
+    fn find_dates_limited_check() {
+        let s = "2023-10-05 then 2023-10-06 then 2023-10-07";
+        let days = |dates: Vec<Result<Date, DateError>>| {
+            dates
+                .into_iter()
+                .map(|date| date.unwrap().day().unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(days(find_dates_limited(s, 2)), vec![5, 6]);
+        assert_eq!(days(find_dates_limited(s, 5)), vec![5, 6, 7]);
+        assert_eq!(find_dates_limited(s, 0), vec![]);
+    }
+
+    #[test]
+    fn precision_key_buckets() {
+        let mut buckets = std::collections::HashMap::new();
+        for date in find_valid_dates(
+            "2023-10-05 and 2023-10-20 and 2023-11-01 and 2023-10 and 2024-10-05 and 2023-11-30",
+        ) {
+            *buckets
+                .entry(PrecisionKey(date, DatePrecision::Month))
+                .or_insert(0) += 1;
+        }
+        assert_eq!(buckets.len(), 3);
+        let october = Date {
+            year: Some(2023),
+            month: Some(10),
+            day: None,
+        };
+        assert_eq!(buckets[&PrecisionKey(october, DatePrecision::Month)], 3);
+        let november = Date {
+            year: Some(2023),
+            month: Some(11),
+            day: Some(15),
+        };
+        assert_eq!(buckets[&PrecisionKey(november, DatePrecision::Month)], 2);
+        assert_eq!(
+            PrecisionKey(october, DatePrecision::Year),
+            PrecisionKey(november, DatePrecision::Year)
+        );
+    }
+
+    #[test]
+    fn explain_resolution_check() {
+        assert_eq!(
+            explain_resolution(&[2023, 10, 5]),
+            "opt1=2023 > 12 ⇒ year first; resolved as YMD; valid"
+        );
+        let explanation = explain_resolution(&[10, 25, 2023]);
+        assert!(explanation.contains("opt2=25 > 12"));
+        assert!(explanation.contains("resolved as MDY"));
+        let explanation = explain_resolution(&[5, 10, 2023]);
+        assert!(explanation.contains("undecided"));
+        assert!(explain_resolution(&[2023, 13, 1]).ends_with("invalid month 13"));
+        assert!(explain_resolution(&[7, 7, 7]).contains("order doesn't matter"));
+        assert!(explain_resolution(&[1]).contains("invalid format"));
+        assert!(explain_resolution(&[13, 2023]).ends_with("invalid month 13"));
+        assert!(explain_resolution(&[1, 0, 0]).contains("no valid month and day"));
+        assert!(explain_resolution(&[25, 10, 2023]).contains("resolved as DMY; valid"));
+        assert_eq!(
+            explain_resolution(&[25, 10, 23]),
+            "opt1=25 > 12 ⇒ year first; resolved as YMD; two-digit year 25 ⇒ 2025; valid"
+        );
+        for parts in [
+            [2023, 10, 5],
+            [10, 25, 2023],
+            [5, 10, 2023],
+            [2023, 13, 1],
+            [25, 10, 2023],
+            [25, 13, 2023],
+            [2023, 2023, 1],
+            [25, 10, 23],
+        ] {
+            let holder = DateHolder {
+                holding: parts
+                    .iter()
+                    .map(|part| Part(part.to_string().chars().collect()))
+                    .collect(),
+            };
+            assert_eq!(
+                holder.as_date().is_ok(),
+                explain_resolution(&parts).ends_with("; valid")
+            );
+        }
+    }
+
+    #[test]
+    fn padded_separators() {
+        assert_eq!(find_dates("2023- 10- 05"), vec![Ok(ymd(2023, 10, 5))]);
+        assert_eq!(find_dates("2023 - 10 - 05"), vec![Ok(ymd(2023, 10, 5))]);
+        assert_eq!(find_dates("2023--10--05"), vec![Ok(ymd(2023, 10, 5))]);
+        assert_eq!(find_dates("on 2023- 10- 05 ok"), vec![Ok(ymd(2023, 10, 5))]);
+        assert_eq!(find_dates("2023-10-05 "), vec![Ok(ymd(2023, 10, 5))]);
+        assert_eq!(find_dates("2023-10-05- "), vec![Ok(ymd(2023, 10, 5))]);
+    }
+
+    #[test]
+    fn timestamp_millis() {
+        let date = Date::from_timestamp_millis(1_696_500_000_123);
+        assert_eq!(date, Date {
+            year: Some(2023),
+            month: Some(10),
+            day: Some(5),
+        });
+        assert_eq!(date.to_timestamp_millis(), Some(1_696_464_000_000));
+        assert_eq!(
+            Date::from_timestamp_millis(date.to_timestamp_millis().unwrap()),
+            date
+        );
+        assert_eq!(Date::from_timestamp_millis(-1), Date {
+            year: Some(1969),
+            month: Some(12),
+            day: Some(31),
+        });
+        assert_eq!(Date::new().to_timestamp_millis(), None);
+        assert_eq!(date.with_day(40).to_timestamp_millis(), None);
+    }
+
+    #[test]
+    fn find_dates_resolved_check() {
+        let options = ParseOptions {
+            learn_order: true,
+            dot_policy: DotPolicy::Ignore,
+            ..Default::default()
+        };
+        let (dates, config) = find_dates_resolved("05-10-23 and 25-12-2023", &options);
+        assert_eq!(dates.len(), 2);
+        assert_eq!(config.learned_order, Some(DateOrder::Dmy));
+        assert_eq!(config.order(), Some(DateOrder::Dmy));
+        assert_eq!(config.separators, vec!['-', '/', '_', ' ']);
+        assert!(config.options.learn_order);
+
+        let (_, config) = find_dates_resolved("2023-10-05", &ParseOptions::default());
+        assert_eq!(config.learned_order, None);
+        assert_eq!(config.order(), None);
+        assert_eq!(config.separators, vec!['-', '/', '_', ' ', '.']);
+    }
+
+    #[test]
+    fn thousands_groups() {
+        let s = "Total: 1.000.000 on 05.10.2023";
+        let dmy = ParseOptions {
+            order: Some(DateOrder::Dmy),
+            ..Default::default()
+        };
+        assert_eq!(find_dates_with(s, &dmy), vec![
+            Err(DateError::InvalidCalendarDate(DateField::Month, 0)),
+            Ok(ymd(2023, 10, 5)),
+        ]);
+        assert_eq!(find_dates("1.000.000"), vec![Err(
+            DateError::InvalidCalendarDate(DateField::Day, 0)
+        )]);
+        let skipping = ParseOptions {
+            skip_thousands_groups: true,
+            ..dmy.clone()
+        };
+        assert_eq!(find_dates_with(s, &skipping), vec![Ok(ymd(2023, 10, 5))]);
+        assert_eq!(
+            find_dates_with("cost 12.500 and 2.000.000", &skipping),
+            vec![]
+        );
+        assert_eq!(find_dates_with("2023.100.05", &skipping).len(), 1);
+        let (dates, diagnostics) = find_dates_with_diagnostics("Total: 1.000.000");
+        assert_eq!(dates, vec![]);
+        assert_eq!(diagnostics.len(), 1);
+    }
+
+    #[test]
+    fn checked_with_day_check() {
+        let february = Date {
+            year: Some(2023),
+            month: Some(2),
+            day: Some(10),
+        };
+        assert_eq!(
+            february.checked_with_day(31),
+            Err(DateError::InvalidCalendarDate(DateField::Day, 31))
+        );
+        assert_eq!(february.with_day(31).day(), Some(31));
+        let march = Date {
+            month: Some(3),
+            ..february
+        };
+        assert_eq!(
+            march.checked_with_day(31),
+            Ok(Date {
+                year: Some(2023),
+                month: Some(3),
+                day: Some(31),
+            })
+        );
+        let leap_february = Date {
+            year: Some(2024),
+            ..february
+        };
+        assert_eq!(leap_february.checked_with_day(29).unwrap().day(), Some(29));
+        assert_eq!(
+            Date::new().checked_with_day(1),
+            Err(DateError::IncompleteDate((None, None, None)))
+        );
+    }
+
+    #[test]
+    fn incremental_parser_check() {
+        let mut parser = IncrementalParser::new();
+        assert_eq!(parser.feed("logged 20"), vec![]);
+        assert_eq!(parser.feed("23-10"), vec![]);
+        assert_eq!(parser.feed("-05 and "), vec![Ok(ymd(2023, 10, 5))]);
+        assert_eq!(parser.finish(), vec![]);
+
+        let mut parser = IncrementalParser::new();
+        assert_eq!(parser.feed("2023-"), vec![]);
+        assert_eq!(parser.feed("10-"), vec![]);
+        assert_eq!(parser.feed("05"), vec![]);
+        assert_eq!(parser.finish(), vec![Ok(ymd(2023, 10, 5))]);
+    }
+
+    #[test]
+    fn qualifiers_check() {
+        let date = |year, month, day| Date {
+            year: Some(year),
+            month,
+            day,
+        };
+        let representative = ParseOptions {
+            qualifiers: QualifierPolicy::Representative,
+            ..Default::default()
+        };
+        let text = "from early Jan 2023 to mid-October 2023 then late 2024";
+        assert_eq!(find_dates_with(text, &representative), vec![
+            Ok(date(2023, Some(1), Some(5))),
+            Ok(date(2023, Some(10), Some(15))),
+            Ok(date(2024, Some(10), None))
+        ]);
+        assert_eq!(
+            find_dates_with("early 2023 or mid 2023", &representative),
+            vec![Ok(date(2023, Some(2), None)), Ok(date(2023, Some(6), None))]
+        );
+        // a written day wins over the qualifier
+        assert_eq!(
+            find_dates_with("late October 5 2023", &representative),
+            vec![Ok(date(2023, Some(10), Some(5)))]
+        );
+
+        let keep = ParseOptions {
+            qualifiers: QualifierPolicy::KeepPrecision,
+            ..Default::default()
+        };
+        assert_eq!(find_dates_with(text, &keep), vec![
+            Ok(date(2023, Some(1), None)),
+            Ok(date(2023, Some(10), None)),
+            Ok(date(2024, None, None))
+        ]);
+        assert_eq!(find_dates(text), vec![
+            Ok(date(2023, Some(1), None)),
+            Ok(date(2023, Some(10), None))
+        ]);
+    }
+
+    #[test]
+    fn find_date_set_check() {
+        let text = "2023-10-05 then 2021-11-21 then 10/05/2023 then 2022.01.31 then 2023-13-40";
+        assert_eq!(find_date_set(text).into_iter().collect::<Vec<_>>(), vec![
+            ymd(2021, 11, 21),
+            ymd(2022, 1, 31),
+            ymd(2023, 10, 5)
+        ]);
+        assert!(find_date_set("nothing here").is_empty());
+    }
+
+    #[test]
+    fn nearest_weekday_check() {
+        // 2023-10-07 is a saturday
+        assert_eq!(ymd(2023, 10, 7).nearest_weekday(), Ok(ymd(2023, 10, 6)));
+        assert_eq!(ymd(2023, 10, 8).nearest_weekday(), Ok(ymd(2023, 10, 9)));
+        assert_eq!(ymd(2023, 10, 5).nearest_weekday(), Ok(ymd(2023, 10, 5)));
+        assert_eq!(
+            ymd(2023, 12, 31).shift_weekend(WeekendShift::Backward),
+            Ok(ymd(2023, 12, 29))
+        );
+        assert_eq!(
+            ymd(2023, 9, 30).shift_weekend(WeekendShift::Forward),
+            Ok(ymd(2023, 10, 2))
+        );
+        assert_eq!(
+            Date {
+                year: Some(2023),
+                month: Some(10),
+                day: None,
+            }
+            .nearest_weekday(),
+            Err(DateError::IncompleteDate((Some(2023), Some(10), None)))
+        );
+    }
+
+    #[test]
+    fn basic_datetime_check() {
+        assert_eq!(find_dates("built 20231005T143000 ok"), vec![Ok(ymd(
+            2023, 10, 5
+        ))]);
+        assert_eq!(find_dates("20231005T143000Z"), vec![Ok(ymd(2023, 10, 5))]);
+        assert_eq!(find_dates("20231005T143000 and 2021-11-21"), vec![
+            Ok(ymd(2023, 10, 5)),
+            Ok(Date {
+                year: Some(2021),
+                month: Some(11),
+                day: Some(21),
+            })
+        ]);
+    }
+
+    #[test]
+    fn date_range_check() {
+        let start = ymd(2023, 12, 30);
+        let end = ymd(2024, 1, 2);
+        let expected = vec![
+            ymd(2023, 12, 30),
+            ymd(2023, 12, 31),
+            ymd(2024, 1, 1),
+            ymd(2024, 1, 2),
+        ];
+        assert_eq!(date_range(&start, &end).collect::<Vec<_>>(), expected);
+        assert_eq!(start.iter_days_until(&end).collect::<Vec<_>>(), expected);
+        assert_eq!(start.iter_days_until(&start).collect::<Vec<_>>(), vec![
+            start
+        ]);
+        assert_eq!(end.iter_days_until(&start).count(), 0);
+        let partial = Date {
+            year: Some(2024),
+            month: Some(1),
+            day: None,
+        };
+        assert_eq!(start.iter_days_until(&partial).count(), 0);
+        assert_eq!(date_range(&partial, &end).count(), 0);
+        assert_eq!(
+            ymd(2023, 2, 30).iter_days_until(&ymd(2023, 3, 3)).count(),
+            0
+        );
+        assert_eq!(date_range(&start, &end.with_day(40)).count(), 0);
+    }
+
+    #[test]
+    fn date_before_punctuation_check() {
+        let date = Date {
+            year: Some(2023),
+            month: Some(10),
+            day: Some(5),
+        };
+        assert_eq!(find_dates("2023-10-05,"), vec![Ok(date)]);
+        assert_eq!(find_dates("2023-10-05;"), vec![Ok(date)]);
+        assert_eq!(find_dates("(2023-10-05)"), vec![Ok(date)]);
+    }
+
+    #[test]
+    fn find_dates_after_keyword_check() {
+        let date = |day| ymd(2023, 10, day);
+        let text = "Created: 2023-10-05, Modified: 2023-10-06";
+        assert_eq!(find_dates_after_keyword(text, "Created"), vec![date(5)]);
+        assert_eq!(find_dates_after_keyword(text, "Modified"), vec![date(6)]);
+        assert_eq!(
+            find_dates_after_keyword(text, "created"),
+            Vec::<Date>::new()
+        );
+        assert_eq!(find_dates_after_keyword_with(text, "created", true), vec![
+            date(5)
+        ]);
+        let text = "Due: soon. Due: 2023-10-07 Due: 2023-10-08";
+        assert_eq!(find_dates_after_keyword(text, "Due:"), vec![
+            date(7),
+            date(8)
+        ]);
+    }
+
+    #[test]
+    fn next_weekday_check() {
+        // 2023-10-04 is a wednesday
+        let wednesday = ymd(2023, 10, 4);
+        assert_eq!(
+            wednesday.next_weekday(Weekday::Monday),
+            Ok(ymd(2023, 10, 9))
+        );
+        assert_eq!(
+            wednesday.next_weekday(Weekday::Thursday),
+            Ok(ymd(2023, 10, 5))
+        );
+        assert_eq!(
+            wednesday.next_weekday(Weekday::Wednesday),
+            Ok(ymd(2023, 10, 11))
+        );
+        assert_eq!(
+            wednesday.roll_forward_to_weekday(Weekday::Wednesday),
+            Ok(wednesday)
+        );
+        assert_eq!(
+            ymd(2023, 12, 29).next_weekday(Weekday::Monday),
+            Ok(ymd(2024, 1, 1))
+        );
+        assert_eq!(
+            Date::new().next_weekday(Weekday::Monday),
+            Err(DateError::IncompleteDate((None, None, None)))
+        );
+    }
+
+    #[test]
+    fn year_first_month_name_check() {
+        assert_eq!(find_dates("2023 October 5"), vec![Ok(ymd(2023, 10, 5))]);
+        assert_eq!(find_dates("released 2023 Oct 05 to everyone"), vec![Ok(
+            ymd(2023, 10, 5)
+        )]);
+        assert_eq!(find_dates("2023-october-5"), vec![Ok(ymd(2023, 10, 5))]);
+        assert_eq!(find_dates("2023 February 30"), vec![Err(
+            DateError::InvalidCalendarDate(DateField::Day, 30)
+        )]);
+    }
+
+    #[test]
+    fn scan_trace_check() {
+        assert_eq!(scan_trace("ab 12 cd 2023-10-05"), vec![
+            TraceEvent::TokenStarted,
+            TraceEvent::PartAdded("12".to_string()),
+            TraceEvent::HolderTruncated("12".to_string()),
+            TraceEvent::TokenStarted,
+            TraceEvent::PartAdded("2023".to_string()),
+            TraceEvent::PartAdded("10".to_string()),
+            TraceEvent::PartAdded("05".to_string()),
+            TraceEvent::HolderPushed("2023 10 05".to_string()),
+            TraceEvent::Resolved(Ok(Date {
+                year: Some(2023),
+                month: Some(10),
+                day: Some(5),
+            })),
+        ]);
+        assert_eq!(scan_trace("no dates"), vec![]);
+    }
+
+    #[test]
+    fn year_month_either_order_check() {
+        let expected = || {
+            Ok(Date {
+                year: Some(2023),
+                month: Some(10),
+                day: None,
+            })
+        };
+        assert_eq!(find_dates("10-2023"), vec![expected()]);
+        assert_eq!(find_dates("2023-10"), vec![expected()]);
+        assert_eq!(find_dates("from 10/2023 to 2023.10"), vec![
+            expected(),
+            expected()
+        ]);
+        assert_eq!(find_dates("13-2023"), vec![Err(
+            DateError::InvalidCalendarDate(DateField::Month, 13)
+        )]);
+    }
+
+    #[test]
+    fn leading_year_is_iso_check() {
+        assert_eq!(find_dates("2023-05-06"), vec![Ok(ymd(2023, 5, 6))]);
+        assert_eq!(find_dates("2023/06/05"), vec![Ok(ymd(2023, 6, 5))]);
+        // a hint can't move a written out year
+        let options = ParseOptions {
+            order: Some(DateOrder::Dmy),
+            ..Default::default()
+        };
+        assert_eq!(find_dates_with("2023-05-06", &options), vec![Ok(ymd(
+            2023, 5, 6
+        ))]);
+        assert_eq!(find_dates("05-06-23"), vec![Err(DateError::UndecidedDate(
+            (Some(5), Some(6), Some(23))
+        ))]);
+    }
+
+    #[test]
+    fn from_parts_checked_check() {
+        assert_eq!(
+            Date::from_parts_checked(Some(2023), Some(13), Some(40)),
+            Err(vec![
+                DateError::InvalidCalendarDate(DateField::Month, 13),
+                DateError::InvalidCalendarDate(DateField::Day, 40)
+            ])
+        );
+        assert_eq!(
+            Date::from_parts_checked(Some(2023), Some(0), Some(31)),
+            Err(vec![DateError::InvalidCalendarDate(DateField::Month, 0)])
+        );
+        assert_eq!(
+            Date::from_parts_checked(Some(2023), Some(2), Some(29)),
+            Err(vec![DateError::InvalidCalendarDate(DateField::Day, 29)])
+        );
+        assert_eq!(
+            Date::from_parts_checked(Some(2024), Some(2), Some(29)),
+            Ok(Date {
+                year: Some(2024),
+                month: Some(2),
+                day: Some(29),
+            })
+        );
+    }
+
+    #[test]
+    fn cjk_markers_check() {
+        let options = ParseOptions {
+            cjk_markers: true,
+            ..Default::default()
+        };
+        assert_eq!(find_dates_with("2023年10月5日", &options), vec![Ok(
+            ymd(2023, 10, 5)
+        )]);
+        assert_eq!(
+            find_dates_with("会議は2023年10月5日です", &options),
+            vec![Ok(ymd(2023, 10, 5))]
+        );
+        assert_eq!(find_dates("2023年10月5日"), vec![]);
+        let (_, config) = find_dates_resolved("", &options);
+        assert!(config.separators.contains(&'年'));
+    }
+
+    #[test]
+    fn weekday_num_check() {
+        // 2023-10-05 is a thursday, 2023-10-08 a sunday
+        assert_eq!(ymd(2023, 10, 5).weekday_num_iso(), Some(4));
+        assert_eq!(ymd(2023, 10, 5).weekday_num_sunday(), Some(4));
+        assert_eq!(ymd(2023, 10, 8).weekday_num_iso(), Some(7));
+        assert_eq!(ymd(2023, 10, 8).weekday_num_sunday(), Some(0));
+        assert_eq!(ymd(2023, 10, 9).weekday_num_iso(), Some(1));
+        assert_eq!(ymd(2023, 10, 9).weekday_num_sunday(), Some(1));
+        let partial = Date {
+            year: Some(2023),
+            month: Some(10),
+            day: None,
+        };
+        assert_eq!(partial.weekday_num_iso(), None);
+        assert_eq!(partial.weekday_num_sunday(), None);
+        assert_eq!(ymd(2023, 12, 40).weekday_num_iso(), None);
+        assert_eq!(ymd(2023, 12, 40).weekday_num_sunday(), None);
+    }
+
+    #[test]
+    fn comma_separator_check() {
+        let comma = ParseOptions {
+            comma_separator: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            find_dates_with("on October 5, 2023 we shipped", &comma),
+            vec![Ok(ymd(2023, 10, 5))]
+        );
+        assert_eq!(find_dates_with("2023,10,05", &comma), vec![Ok(ymd(
+            2023, 10, 5
+        ))]);
+        let text = "sold 1,234,567 units by 2023-10-05";
+        assert_eq!(find_dates_with(text, &comma), vec![
+            Err(DateError::InvalidCalendarDate(DateField::Day, 234)),
+            Ok(ymd(2023, 10, 5))
+        ]);
+        let guarded = ParseOptions {
+            skip_thousands_groups: true,
+            ..comma.clone()
+        };
+        assert_eq!(find_dates_with(text, &guarded), vec![Ok(ymd(2023, 10, 5))]);
+        assert_eq!(find_dates_with("October 5, 2023", &guarded), vec![Ok(ymd(
+            2023, 10, 5
+        ))]);
+        assert!(
+            !find_dates_resolved("", &ParseOptions::default())
+                .1
+                .separators
+                .contains(&',')
+        );
+    }
+
+    #[test]
+    fn is_today_check() {
+        assert!(Date::today().is_today());
+        let today = Date::today();
+        assert!(!(today + Days(1)).unwrap().is_today());
+        assert!(!today.truncate_to(DatePrecision::Month).is_today());
+    }
+
+    #[test]
+    fn date_parser_check() {
+        const fn assert_send_sync<T: Send + Sync + Clone>() {}
+        const _: () = assert_send_sync::<DateParser>();
+
+        let parser = DateParser::new(ParseOptions {
+            order: Some(DateOrder::Dmy),
+            ..Default::default()
+        });
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let parser = parser.clone();
+                std::thread::spawn(move || parser.find_dates("05/10/2023"))
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), vec![Ok(ymd(2023, 10, 5))]);
+        }
+        assert_eq!(parser.find_dates("05/10/2023"), vec![Ok(ymd(2023, 10, 5))]);
+        assert_eq!(parser.options().order, Some(DateOrder::Dmy));
+    }
+
+    #[test]
+    fn duplicate_year_check() {
+        assert_eq!(find_dates("2023-2023-10"), vec![Err(
+            DateError::DuplicateComponent(2023)
+        )]);
+        let options = ParseOptions {
+            collapse_duplicate_year: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            find_dates_with("2023-2023-10 and 2023/2023/10/05", &options),
+            vec![
+                Ok(Date {
+                    year: Some(2023),
+                    month: Some(10),
+                    day: None,
+                }),
+                Ok(Date {
+                    year: Some(2023),
+                    month: Some(10),
+                    day: Some(5),
+                })
+            ]
+        );
+        assert_eq!(
+            DateError::DuplicateComponent(2023).to_string(),
+            "Year 2023 is written twice"
+        );
+    }
+
+    #[test]
+    fn accessors_check() {
+        let full = find_last_date("shipped on 2023-10-05").unwrap();
+        assert_eq!(
+            (full.year(), full.month(), full.day()),
+            (Some(2023), Some(10), Some(5))
+        );
+        let partial = find_last_date("billed for 10/2023").unwrap();
+        assert_eq!(
+            (partial.year(), partial.month(), partial.day()),
+            (Some(2023), Some(10), None)
+        );
+        let empty = Date::new();
+        assert_eq!(
+            (empty.year(), empty.month(), empty.day()),
+            (None, None, None)
+        );
+    }
+
+    #[test]
+    fn add_months_check() {
+        let date = |year, month, day| Date {
+            year: Some(year),
+            month: Some(month),
+            day,
+        };
+        assert_eq!(
+            date(2023, 10, Some(5)).add_months(1),
+            Ok(date(2023, 11, Some(5)))
+        );
+        assert_eq!(
+            date(2023, 11, Some(5)).add_months(3),
+            Ok(date(2024, 2, Some(5)))
+        );
+        assert_eq!(date(2023, 1, None).add_months(-1), Ok(date(2022, 12, None)));
+        assert_eq!(
+            date(2023, 10, None).add_months(-22),
+            Ok(date(2021, 12, None))
+        );
+        assert_eq!(
+            date(2023, 1, Some(31)).add_months(1),
+            Ok(date(2023, 2, Some(28)))
+        );
+        assert_eq!(
+            date(2024, 1, Some(31)).add_months(1),
+            Ok(date(2024, 2, Some(29)))
+        );
+        assert_eq!(
+            date(2023, 5, Some(31)).add_months(-1),
+            Ok(date(2023, 4, Some(30)))
+        );
+        assert_eq!(
+            date(0, 1, None).add_months(-1),
+            Err(DateError::YearOutOfRange(-1))
+        );
+        assert_eq!(
+            Date::new().add_months(1),
+            Err(DateError::IncompleteDate((None, None, None)))
+        );
+    }
+
+    #[test]
+    fn date_display_check() {
+        let date = |year, month, day| Date { year, month, day };
+        assert_eq!(
+            date(Some(2023), Some(10), Some(5)).to_string(),
+            "2023-10-05"
+        );
+        assert_eq!(date(Some(2023), Some(5), None).to_string(), "2023-05");
+        assert_eq!(date(Some(2023), None, None).to_string(), "2023");
+        assert_eq!(date(Some(800), Some(1), Some(1)).to_string(), "0800-01-01");
+        assert_eq!(Date::new().to_string(), "");
+        assert_eq!(date(None, Some(10), Some(5)).to_string(), "");
+        assert_eq!(
+            find_last_date("on 5/20/2023").unwrap().to_string(),
+            "2023-05-20"
+        );
+    }
+
+    #[test]
+    fn from_str_check() {
+        assert_eq!("2023-10-05".parse::<Date>(), Ok(ymd(2023, 10, 5)));
+        assert_eq!("5 Oct 2023".parse::<Date>(), Ok(ymd(2023, 10, 5)));
+        assert_eq!(
+            "2023-10".parse::<Date>(),
+            Ok(Date {
+                year: Some(2023),
+                month: Some(10),
+                day: None,
+            })
+        );
+        for s in [
+            "2023-10-05 is due",
+            "on 2023-10-05",
+            "2023-10-05 2023-10-06 x",
+            "",
+        ] {
+            assert_eq!(
+                s.parse::<Date>(),
+                Err(DateError::InvalidDateFormat(s.to_string()))
+            );
+        }
+        assert_eq!(
+            "2023-10-05, 2023-10-06".parse::<Date>(),
+            Err(DateError::InvalidDateFormat(
+                "2023-10-05, 2023-10-06".to_string()
+            ))
+        );
+        assert_eq!(
+            "2023-13-05".parse::<Date>(),
+            Err(DateError::InvalidCalendarDate(DateField::Month, 13))
+        );
+        assert_eq!(
+            "2023".parse::<Date>(),
+            Ok(Date {
+                year: Some(2023),
+                month: None,
+                day: None
+            })
+        );
+    }
+
+    #[test]
+    fn add_years_check() {
+        assert_eq!(ymd(2024, 2, 29).add_years(1), Ok(ymd(2025, 2, 28)));
+        assert_eq!(ymd(2024, 2, 29).add_years(4), Ok(ymd(2028, 2, 29)));
+        assert_eq!(ymd(2024, 2, 29).add_years(-4), Ok(ymd(2020, 2, 29)));
+        assert_eq!(ymd(2023, 10, 5).add_years(-23), Ok(ymd(2000, 10, 5)));
+        let year_only = Date {
+            year: Some(2023),
+            month: None,
+            day: None,
+        };
+        assert_eq!(
+            year_only.add_years(2).map(|date| date.year()),
+            Ok(Some(2025))
+        );
+        assert_eq!(
+            ymd(1, 1, 1).add_years(-2),
+            Err(DateError::YearOutOfRange(-1))
+        );
+        assert_eq!(
+            Date::new().add_years(1),
+            Err(DateError::IncompleteDate((None, None, None)))
+        );
+    }
+
+    #[test]
+    fn find_dates_in_url_check() {
+        let date = |day| ymd(2023, 10, day);
+        assert_eq!(
+            find_dates_in_url("https://example.com/blog/2023/10/05/post-title"),
+            vec![date(5)]
+        );
+        assert_eq!(
+            find_dates_in_url("https://example.com/search?from=2023-10-01&to=2023%2D10%2D31#top"),
+            vec![date(1), date(31)]
+        );
+        assert_eq!(find_dates_in_url("/archive?day=2023%2F10%2F05"), vec![
+            date(5)
+        ]);
+        assert_eq!(
+            find_dates_in_url("/items?page=10&size=12"),
+            Vec::<Date>::new()
+        );
+        assert_eq!(find_dates_in_url("/2023/10/05/123-foo"), vec![date(5)]);
+        assert_eq!(find_dates_in_url("/2023-10-05/123-foo"), vec![date(5)]);
+        assert_eq!(find_dates_in_url("/a/2023/10/5/b"), vec![date(5)]);
+        assert_eq!(find_dates_in_url("/2023/10/05-notes"), vec![date(5)]);
+        assert_eq!(find_dates_in_url("/2023/10/05/2023/10/06"), vec![
+            date(5),
+            date(6)
+        ]);
+        assert_eq!(path_date_runs("/2023/10/05/123-foo/"), vec![
+            "",
+            "2023/10/05",
+            "123-foo",
+            ""
+        ]);
+        assert_eq!(percent_decode("100%25 %zz %+1 %4"), "100% %zz %+1 %4");
+    }
+
+    #[test]
+    fn day_first_month_first_check() {
+        let date = |month, day| Ok(ymd(2023, month, day));
+        assert_eq!(
+            find_dates_with("03-04-2023", &ParseOptions::day_first()),
+            vec![date(4, 3)]
+        );
+        assert_eq!(
+            find_dates_with("03-04-2023", &ParseOptions::month_first()),
+            vec![date(3, 4)]
+        );
+        assert_eq!(find_dates("03-04-2023"), vec![Err(
+            DateError::UndecidedDate((Some(3), Some(4), Some(2023)))
+        )]);
+        // two part dates only resolve with a year to give them
+        let day_first = ParseOptions {
+            assume_year: Some(2023),
+            ..ParseOptions::day_first()
+        };
+        assert_eq!(find_dates_with("03-04", &day_first), vec![date(4, 3)]);
+    }
+
+    #[test]
+    fn bind_on_whitespace_check() {
+        let date = |day| Ok(ymd(2023, 10, day));
+        assert_eq!(find_dates("2023 10 05"), vec![date(5)]);
+        let strict = ParseOptions {
+            bind_on_whitespace: false,
+            ..Default::default()
+        };
+        assert_eq!(find_dates_with("2023 10 05", &strict), vec![]);
+        assert_eq!(find_dates_with("2023-10-05 2023-10-06", &strict), vec![
+            date(5),
+            date(6)
+        ]);
+        assert!(!find_dates_resolved("", &strict).1.separators.contains(&' '));
+    }
+
+    #[test]
+    fn day_first_with_day_above_twelve() {
+        let date = |month, day| Ok(ymd(2023, month, day));
+        assert_eq!(find_dates("25-10-2023"), vec![date(10, 25)]);
+        assert_eq!(find_dates("due 13/01/2023 or 31.12.2023"), vec![
+            date(1, 13),
+            date(12, 31)
+        ]);
+        assert_eq!(find_dates("25-13-2023"), vec![Err(
+            DateError::InvalidCalendarDate(DateField::Month, 13)
+        )]);
+        // without a written out year a first part above 12 is still the year
+        assert_eq!(find_dates("23-10-05"), vec![Ok(Date {
+            year: Some(2023),
+            month: Some(10),
+            day: Some(5),
+        })]);
+    }
+
+    #[test]
+    fn to_short_string_check() {
+        let date = |year, month, day| Date {
+            year: Some(year),
+            month: Some(month),
+            day,
+        };
+        assert_eq!(
+            date(2023, 10, Some(5)).to_short_string('/'),
+            Some("23/10/05".to_string())
+        );
+        assert_eq!(
+            date(1999, 1, Some(31)).to_short_string('-'),
+            Some("99-01-31".to_string())
+        );
+        assert_eq!(
+            date(2005, 6, Some(7)).to_short_string('.'),
+            Some("05.06.07".to_string())
+        );
+        assert_eq!(date(2023, 10, None).to_short_string('/'), None);
+        assert_eq!(date(2023, 2, Some(30)).to_short_string('/'), None);
+        assert_eq!(date(2023, 13, Some(1)).to_short_string('/'), None);
+    }
+
+    #[test]
+    fn validate_check() {
+        let date = |month, day| ymd(2023, month, day);
+        assert_eq!(date(10, 5).validate(), Ok(()));
+        assert_eq!(
+            date(13, 5).validate(),
+            Err(DateError::InvalidCalendarDate(DateField::Month, 13))
+        );
+        assert_eq!(
+            date(10, 32).validate(),
+            Err(DateError::InvalidCalendarDate(DateField::Day, 32))
+        );
+        assert_eq!(
+            date(10, 0).validate(),
+            Err(DateError::InvalidCalendarDate(DateField::Day, 0))
+        );
+        assert_eq!(find_dates("2023-99-99"), vec![Err(
+            DateError::InvalidCalendarDate(DateField::Month, 99)
+        )]);
+        assert_eq!(find_dates("2023-10-32 and 2023-10-00"), vec![
+            Err(DateError::InvalidCalendarDate(DateField::Day, 32)),
+            Err(DateError::InvalidCalendarDate(DateField::Day, 0))
+        ]);
+    }
+
+    #[test]
+    fn find_date_ranges_check() {
+        let date = |month, day| ymd(2023, month, day);
+        assert_eq!(find_date_ranges("Oct 5-10, 2023"), vec![(
+            date(10, 5),
+            date(10, 10)
+        )]);
+        assert_eq!(
+            find_date_ranges("held 28-30 September 2023 and 2023-10-05/2023-10-10"),
+            vec![(date(9, 28), date(9, 30)), (date(10, 5), date(10, 10))]
+        );
+        assert_eq!(find_date_ranges("Oct 10-5, 2023"), vec![]);
+        assert_eq!(find_date_ranges("Feb 27-30, 2023"), vec![]);
+        assert_eq!(find_date_ranges("Oct 5, 2023"), vec![]);
+    }
+
+    #[test]
+    fn impossible_calendar_dates_check() {
+        for s in ["2023-02-30", "2023-04-31", "2023-02-29", "1900-02-29"] {
+            assert_eq!(
+                find_dates(s),
+                vec![Err(DateError::InvalidCalendarDate(
+                    DateField::Day,
+                    s[8..].parse().unwrap()
+                ))],
+                "{s}"
+            );
+            assert!(
+                !s.parse::<Date>()
+                    .is_ok_and(|date| date.is_valid_calendar_date())
+            );
+        }
+        for s in ["2024-02-29", "2000-02-29", "2023-04-30"] {
+            assert!(s.parse::<Date>().unwrap().is_valid_calendar_date(), "{s}");
+        }
+        let partial = Date {
+            year: Some(2023),
+            month: Some(2),
+            day: None,
+        };
+        assert!(!partial.is_valid_calendar_date());
+    }
+
+    #[test]
+    fn detect_dominant_format_check() {
+        let text =
+            "25/10/2023, 13.01.2022, 05/10/2023 and 30-11-2021, then 2023-10-05 and 10/25/2023";
+        assert_eq!(detect_dominant_format(text), Some(DateOrder::Dmy));
+        assert_eq!(detect_dominant_format("2023-10-05 or 10/25/2023"), None);
+        assert_eq!(detect_dominant_format("2023-10-05"), Some(DateOrder::Ymd));
+        assert_eq!(detect_dominant_format("05/10/2023"), None);
+    }
+
+    #[test]
+    fn month_names_check() {
+        assert_eq!(find_dates("05 Oct 2023"), vec![Ok(ymd(2023, 10, 5))]);
+        assert_eq!(find_dates("October 5, 2023"), vec![Ok(ymd(2023, 10, 5))]);
+        assert_eq!(find_dates("5 Oct, 2023"), vec![Ok(ymd(2023, 10, 5))]);
+        assert_eq!(find_dates("Jan-01-2000"), vec![Ok(ymd(2000, 1, 1))]);
+        assert_eq!(find_dates("Oct 5-10, 2023"), vec![Err(
+            DateError::InvalidDateFormat("oct 5 10".to_string())
+        )]);
+        assert_eq!(find_dates("05 Oct 2023, 2023-10-06"), vec![
+            Ok(ymd(2023, 10, 5)),
+            Ok(ymd(2023, 10, 6))
+        ]);
+        assert_eq!(find_dates("on DECEMBER 31, 1999, at midnight"), vec![Ok(
+            ymd(1999, 12, 31)
+        )]);
+        // words that aren't months still end a date in progress
+        assert_eq!(find_dates("10 octopus 2023"), vec![]);
+        assert_eq!(find_dates("12 foo 2023-10-05"), vec![Ok(ymd(2023, 10, 5))]);
+        assert_eq!(find_dates("see you in May"), vec![]);
+        assert_eq!(find_dates("Oct 5"), vec![Err(DateError::IncompleteDate((
+            None,
+            Some(10),
+            Some(5)
+        )))]);
+    }
+
+    #[test]
+    fn find_first_date_check() {
+        let s = "2023-13-05 then 2021-11-21 and later 2023-10-05";
+        assert_eq!(
+            find_first_date(s),
+            Err(DateError::InvalidCalendarDate(DateField::Month, 13))
+        );
+        assert_eq!(
+            find_first_date(&s[11..]),
+            Ok(Date {
+                year: Some(2021),
+                month: Some(11),
+                day: Some(21),
+            })
+        );
+        assert_eq!(
+            find_first_date("no dates here"),
+            Err(DateError::NoDatesFound("no dates here".to_string()))
+        );
+    }
+
+    #[test]
+    fn iso_week_check() {
+        assert_eq!(find_dates("week date 2023-W40-4"), vec![Ok(ymd(
+            2023, 10, 5
+        ))]);
+        assert_eq!(find_dates("2020-w53-7"), vec![Ok(ymd(2021, 1, 3))]);
+        for (date, week) in [
+            (ymd(2023, 10, 5), "2023-W40-4"),
+            (ymd(2019, 12, 30), "2020-W01-1"),
+            (ymd(2020, 12, 31), "2020-W53-4"),
+            (ymd(2021, 1, 3), "2020-W53-7"),
+            (ymd(2024, 12, 30), "2025-W01-1"),
+            (ymd(2023, 1, 1), "2022-W52-7"),
+        ] {
+            assert_eq!(date.format_iso_week().as_deref(), Some(week));
+            assert_eq!(find_dates(week), vec![Ok(date)]);
+        }
+        assert_eq!(find_dates("2023-W53-1"), vec![Err(
+            DateError::InvalidCalendarDate(DateField::Week, 53)
+        )]);
+        assert_eq!(
+            Date::from_iso_week(2023, 40, 8),
+            Err(DateError::InvalidCalendarDate(DateField::Weekday, 8))
+        );
+        assert_eq!(find_dates("2023 w 5"), vec![]);
+        assert_eq!(find_dates("in 2023 W40 sales were up"), vec![]);
+        assert_eq!(find_dates("2023-W40"), vec![Err(
+            DateError::InvalidDateFormat("2023 w40".to_string())
+        )]);
+    }
+
+    #[test]
+    fn range_contains_check() {
+        let date = |year, month, day| Date { year, month, day };
+        let year = date(Some(2023), None, None);
+        let october = date(Some(2023), Some(10), None);
+        assert!(year.range_contains(&date(Some(2023), Some(10), Some(5))));
+        assert!(year.range_contains(&date(Some(2023), Some(12), Some(31))));
+        assert!(!year.range_contains(&date(Some(2024), Some(1), Some(1))));
+        assert!(year.range_contains(&october));
+        assert!(!october.range_contains(&year));
+        assert!(october.range_contains(&date(Some(2023), Some(10), Some(31))));
+        assert!(!october.range_contains(&date(Some(2023), Some(11), Some(1))));
+        assert!(october.range_contains(&october));
+        assert!(Date::new().range_contains(&october));
+    }
+
+    #[test]
+    fn iter_dates_check() {
+        for s in [
+            "2023-10-05 some other/random text 2021-11_21",
+            "05-10-23 and 2023-13-01 and 10/2023",
+            "October 5, 2023 @1696464000 x 2023-W40-4",
+            "",
+            "nothing at all",
+        ] {
+            assert_eq!(iter_dates(s).collect::<Vec<_>>(), find_dates(s), "{s}");
+        }
+        let first_2021 = iter_dates("2023-10-05 2020 x 2021-11-21 then 2021-12-01")
+            .flatten()
+            .find(|date| date.year() == Some(2021));
+        assert_eq!(first_2021.and_then(|date| date.month()), Some(11));
+    }
+
+    #[test]
+    fn incremental_spans_check() {
+        let text = "café 2023-10-05 → naïve 05 Oct 2023, then 2021-11-21";
+        let mut whole = IncrementalParser::new();
+        let mut expected = whole.feed_with_spans(text);
+        expected.extend(whole.finish_with_spans());
+        let slices: Vec<&str> = expected
+            .iter()
+            .map(|(_, span)| &text[span.clone()])
+            .collect();
+        assert_eq!(slices, vec!["2023-10-05", "05 Oct 2023", "2021-11-21"]);
+
+        // every chunk size splits some multi-byte character
+        for size in 1..8 {
+            let mut parser = IncrementalParser::new();
+            let mut dates = vec![];
+            for chunk in text.as_bytes().chunks(size) {
+                dates.extend(parser.feed_bytes_with_spans(chunk));
+            }
+            dates.extend(parser.finish_with_spans());
+            assert_eq!(dates, expected, "chunks of {size}");
+        }
+
+        let mut parser = IncrementalParser::new();
+        let mut dates = parser.feed_bytes_with_spans(b"2023-10-05\xff2021-11-21 \xe2\x82");
+        dates.extend(parser.finish_with_spans());
+        let spans: Vec<Range<usize>> = dates.into_iter().map(|(_, span)| span).collect();
+        assert_eq!(spans, vec![0..10, 11..21]);
+    }
+
+    #[test]
+    fn find_dates_spans_check() {
+        let s = "2023-10-05 and 2021-11-21";
+        let spans = find_dates_spans(s);
+        let slices: Vec<&str> = spans.iter().map(|(_, span)| &s[span.clone()]).collect();
+        assert_eq!(slices, vec!["2023-10-05", "2021-11-21"]);
+        assert_eq!(
+            spans.into_iter().map(|(date, _)| date).collect::<Vec<_>>(),
+            find_dates(s)
+        );
+        let s = "due: October 5, 2023. paid @1696464000 ok 20231005T143000Z";
+        let slices: Vec<&str> = find_dates_spans(s)
+            .into_iter()
+            .map(|(_, span)| &s[span])
+            .collect();
+        assert_eq!(slices, vec!["October 5, 2023", "@1696464000", "20231005"]);
+    }
+
+    #[test]
+    fn from_parts_check() {
+        assert_eq!(
+            Date::from_parts(Some(2023), None, Some(5)),
+            Err(DateError::OrphanedComponent(DateField::Day))
+        );
+        assert_eq!(
+            Date::from_parts(None, Some(10), None),
+            Err(DateError::OrphanedComponent(DateField::Month))
+        );
+        assert_eq!(
+            Date::from_parts(Some(2023), Some(2), Some(29)),
+            Err(DateError::InvalidCalendarDate(DateField::Day, 29))
+        );
+        assert_eq!(
+            Date::from_parts(Some(2023), Some(10), None),
+            Ok(Date {
+                year: Some(2023),
+                month: Some(10),
+                day: None,
+            })
+        );
+        assert_eq!(Date::from_parts(None, None, None), Ok(Date::new()));
+        assert_eq!(
+            DateError::OrphanedComponent(DateField::Day).to_string(),
+            "A day can't be given without the components above it"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_check() {
+        let dates = [
+            Date {
+                year: Some(2023),
+                month: Some(10),
+                day: Some(5),
+            },
+            Date {
+                year: Some(2023),
+                month: Some(10),
+                day: None,
+            },
+            Date {
+                year: Some(2023),
+                month: None,
+                day: None,
+            },
+            Date {
+                year: Some(5),
+                month: Some(10),
+                day: None,
+            },
+        ];
+        for (date, json) in
+            dates
+                .iter()
+                .zip(["\"2023-10-05\"", "\"2023-10\"", "\"2023\"", "\"0005-10\""])
+        {
+            assert_eq!(serde_json::to_string(date).unwrap(), json);
+            assert_eq!(serde_json::from_str::<Date>(json).unwrap(), *date);
+        }
+        assert!(serde_json::from_str::<Date>("\"2023-13-05\"").is_err());
+        assert!(serde_json::from_str::<Date>("20231005").is_err());
+    }
+
+    #[test]
+    fn year_fraction_check() {
+        assert_eq!(ymd(2023, 1, 1).year_fraction(), Some(0.0));
+        assert_eq!(ymd(2024, 1, 1).year_fraction(), Some(0.0));
+        assert_eq!(ymd(2023, 12, 31).year_fraction(), Some(364.0 / 365.0));
+        assert_eq!(ymd(2024, 12, 31).year_fraction(), Some(365.0 / 366.0));
+        assert_eq!(ymd(2024, 7, 2).year_fraction(), Some(0.5));
+        assert_eq!(ymd(2023, 10, 1).with_day(0).year_fraction(), None);
+        assert_eq!(ymd(2023, 12, 1).with_day(40).year_fraction(), None);
+        assert_eq!(
+            Date {
+                year: Some(2023),
+                month: Some(10),
+                day: None
+            }
+            .year_fraction(),
+            None
+        );
+    }
+
+    #[test]
+    fn sort_partial_dates_check() {
+        let date = |year, month, day| Date {
+            year: Some(year),
+            month,
+            day,
+        };
+        let mut dates = vec![
+            date(2023, Some(11), None),
+            date(2023, Some(10), Some(5)),
+            date(2022, Some(12), Some(31)),
+            date(2023, None, None),
+            date(2023, Some(10), None),
+            date(2023, Some(10), Some(1)),
+        ];
+        dates.sort();
+        assert_eq!(dates, [
+            date(2022, Some(12), Some(31)),
+            date(2023, None, None),
+            date(2023, Some(10), None),
+            date(2023, Some(10), Some(1)),
+            date(2023, Some(10), Some(5)),
+            date(2023, Some(11), None),
+        ]);
+        let counts = std::collections::BTreeMap::from([(dates[1], 1), (dates[0], 2)]);
+        assert_eq!(counts.keys().next(), Some(&dates[0]));
+    }
+
+    #[test]
+    fn find_year_ranges_check() {
+        assert_eq!(find_year_ranges("(1920–1995)"), vec![(1920, 1995)]);
+        assert_eq!(
+            find_year_ranges("Ada Lovelace (1815–1852) and Alan Turing (1912-1954)"),
+            vec![(1815, 1852), (1912, 1954)]
+        );
+        assert_eq!(find_year_ranges("(1995–1920)"), vec![]);
+        assert_eq!(find_year_ranges("on 2023-10-05 and 2023-10"), vec![]);
+        assert_eq!(find_year_ranges("(20–95)"), vec![]);
+    }
+
+    #[test]
+    fn hash_set_check() {
+        let date = |year, month, day| Date {
+            year: Some(year),
+            month,
+            day,
+        };
+        let dates = std::collections::HashSet::from([
+            date(2023, Some(10), Some(5)),
+            date(2023, Some(10), Some(5)),
+            date(2023, Some(10), None),
+            date(2023, None, None),
+            date(2023, Some(10), None),
+        ]);
+        assert_eq!(dates.len(), 3);
+        let mut counts = std::collections::HashMap::new();
+        for date in find_valid_dates("2023-10-05 and 5 Oct 2023 then 2023-10-06") {
+            *counts.entry(date).or_insert(0) += 1;
+        }
+        assert_eq!(counts[&date(2023, Some(10), Some(5))], 2);
+        assert_eq!(counts.len(), 2);
+    }
+
+    #[test]
+    fn from_ymd_str_strict_check() {
+        assert_eq!(
+            Date::from_ymd_str_strict("2023-10-05"),
+            Ok(Date {
+                year: Some(2023),
+                month: Some(10),
+                day: Some(5)
+            })
+        );
+        for s in [
+            "2023-1-5",
+            "2023/10/05",
+            " 2023-10-05",
+            "2023-10-05 ",
+            "2023-10",
+            "23-10-05",
+            "2023-10-0x",
+            "2023-１0-05",
+        ] {
+            assert_eq!(
+                Date::from_ymd_str_strict(s),
+                Err(DateError::FormatMismatch(s.to_string()))
+            );
+        }
+        assert_eq!(
+            Date::from_ymd_str_strict("2023-02-30"),
+            Err(DateError::InvalidCalendarDate(DateField::Day, 30))
+        );
+    }
+
+    #[test]
+    fn find_unique_dates_check() {
+        let date = Date {
+            year: Some(2023),
+            month: Some(10),
+            day: Some(5),
+        };
+        assert_eq!(
+            find_unique_dates("2023-10-05 and 2023-10-05 then 5 Oct 2023"),
+            vec![Ok(date)]
+        );
+        let found = find_unique_dates("5/6/2023 and 2023-10-05 then 5/6/2023 and 2023-10-05");
+        assert_eq!(found.len(), 3);
+        assert_eq!(found[1], Ok(date));
+        assert!(found[0].is_err() && found[2].is_err());
+    }
+
+    #[test]
+    fn season_check() {
+        let month = |month| Date {
+            year: Some(2023),
+            month: Some(month),
+            day: None,
+        };
+        assert_eq!(month(10).season(Hemisphere::Northern), Some(Season::Autumn));
+        assert_eq!(month(10).season(Hemisphere::Southern), Some(Season::Spring));
+        assert_eq!(month(12).season(Hemisphere::Northern), Some(Season::Winter));
+        assert_eq!(month(1).season(Hemisphere::Southern), Some(Season::Summer));
+        assert_eq!(month(7).season(Hemisphere::Southern), Some(Season::Winter));
+        assert_eq!(month(13).season(Hemisphere::Northern), None);
+        assert_eq!(
+            Date {
+                year: Some(2023),
+                month: None,
+                day: None
+            }
+            .season(Hemisphere::Northern),
+            None
+        );
+    }
 
-impl std::error::Error for DateError {}
+    #[test]
+    fn two_digit_year_pivot_check() {
+        let date = |year| Ok(ymd(year, 5, 6));
+        let us = ParseOptions::month_first();
+        assert_eq!(find_dates_with("05/06/99", &us), vec![date(1999)]);
+        assert_eq!(find_dates_with("05/06/20", &us), vec![date(2020)]);
+        assert_eq!(find_dates_with("05/06/69", &us), vec![date(1969)]);
+        assert_eq!(find_dates_with("05/06/68", &us), vec![date(2068)]);
+        assert_eq!(find_dates("6 May 99"), vec![date(1999)]);
+        assert_eq!(find_dates("0099-05-06"), vec![date(99)]);
+        let pivot = |two_digit_year_pivot| ParseOptions {
+            two_digit_year_pivot,
+            ..ParseOptions::month_first()
+        };
+        assert_eq!(find_dates_with("05/06/45", &pivot(Some(30))), vec![date(
+            1945
+        )]);
+        assert_eq!(find_dates_with("05/06/99", &pivot(None)), vec![date(99)]);
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
     #[test]
-    fn part_to_u16_check_1() {
-        let part = Part(vec!['2', '0', '2', '3']);
-        let num = part.to_u16().unwrap();
-        assert_eq!(num, 2023);
+    fn min_max_date_check() {
+        let date = |year, month, day| Date {
+            year: Some(year),
+            month,
+            day,
+        };
+        let dates = [
+            date(2023, Some(10), Some(5)),
+            date(2022, Some(12), Some(31)),
+            date(2023, None, None),
+        ];
+        assert_eq!(min_date(&dates), Some(&date(2022, Some(12), Some(31))));
+        assert_eq!(max_date(&dates), Some(&date(2023, Some(10), Some(5))));
+        assert_eq!(
+            min_date(&[dates[0], dates[2]]),
+            Some(&date(2023, None, None))
+        );
+        assert_eq!(min_date(&[]), None);
+        assert_eq!(max_date(&[]), None);
     }
+
     #[test]
-    fn part_to_u16_check_2() {
-        let part = Part(vec!['0', '5']);
-        let num = part.to_u16().unwrap();
-        assert_eq!(num, 5);
+    fn plus_ends_date_check() {
+        assert_eq!(find_dates("2023-10-05+extra"), vec![Ok(ymd(2023, 10, 5))]);
+        assert_eq!(find_dates("2023-10-05+00:00"), vec![Ok(ymd(2023, 10, 5))]);
+        assert_eq!(find_dates("at 2023-10-05+0200 sharp"), vec![Ok(ymd(
+            2023, 10, 5
+        ))]);
+        assert_eq!(find_dates("+2023-10-05"), vec![Ok(ymd(2023, 10, 5))]);
     }
 
     #[test]
-    fn date_holders_check_1() {
-        let dates = find_dates("2023-10-05");
-        let expected = vec![Ok(Date {
+    fn find_dates_indexed_check() {
+        assert_eq!(
+            find_dates_indexed(&[
+                "no dates",
+                "due 2023-10-05",
+                "",
+                "2023-10-05 and 2024-02-29"
+            ]),
+            vec![
+                (0, vec![]),
+                (1, vec![Ok(ymd(2023, 10, 5))]),
+                (2, vec![]),
+                (3, vec![Ok(ymd(2023, 10, 5)), Ok(ymd(2024, 2, 29))]),
+            ]
+        );
+        assert_eq!(find_dates_indexed(&[]), vec![]);
+    }
+
+    #[test]
+    fn days_between_check() {
+        assert_eq!(days_between(&ymd(2023, 12, 31), &ymd(2024, 1, 1)), Ok(1));
+        assert_eq!(days_between(&ymd(2023, 1, 1), &ymd(2024, 1, 1)), Ok(365));
+        assert_eq!(days_between(&ymd(2024, 1, 1), &ymd(2025, 1, 1)), Ok(366));
+        assert_eq!(days_between(&ymd(2024, 2, 28), &ymd(2024, 3, 1)), Ok(2));
+        assert_eq!(days_between(&ymd(2024, 3, 1), &ymd(2024, 2, 28)), Ok(-2));
+        assert_eq!(
+            days_between(&ymd(1999, 12, 31), &ymd(2000, 12, 31)),
+            Ok(366)
+        );
+        let partial = Date {
             year: Some(2023),
             month: Some(10),
-            day: Some(5),
-        })];
-        assert_eq!(dates, expected);
+            day: None,
+        };
+        assert_eq!(
+            days_between(&partial, &ymd(2024, 1, 1)),
+            Err(DateError::IncompleteDate((Some(2023), Some(10), None)))
+        );
     }
+
     #[test]
-    fn date_holders_check_2() {
-        let dates = find_dates("2023-10-05 some other/random text 2021-11_21");
-        let expected = vec![
-            Ok(Date {
+    fn parse_optional_date_check() {
+        for s in ["N/A", "null", "None", "NULL", " - ", "", "na"] {
+            assert_eq!(parse_optional_date(s), Ok(None), "{s}");
+        }
+        assert_eq!(
+            parse_optional_date(" 2023-10-05 "),
+            Ok(Some(Date {
                 year: Some(2023),
                 month: Some(10),
-                day: Some(5),
-            }),
-            Ok(Date {
-                year: Some(2021),
-                month: Some(11),
-                day: Some(21),
-            }),
-        ];
-        assert_eq!(dates, expected);
+                day: Some(5)
+            }))
+        );
+        assert_eq!(
+            parse_optional_date("nothing"),
+            Err(DateError::InvalidDateFormat("nothing".to_string()))
+        );
+        assert_eq!(
+            parse_optional_date("2023-02-30"),
+            Err(DateError::InvalidCalendarDate(DateField::Day, 30))
+        );
     }
+
     #[test]
-    fn date_holders_check_3() {
-        let dates = find_dates("100 some random text 2023-10-05 some other/random text 2021-11_21");
-        let expected = vec![
-            Ok(Date {
+    fn weekday_check() {
+        assert_eq!(ymd(2023, 10, 5).weekday(), Ok(Weekday::Thursday));
+        assert_eq!(ymd(2000, 1, 1).weekday(), Ok(Weekday::Saturday));
+        assert_eq!(ymd(1970, 1, 1).weekday(), Ok(Weekday::Thursday));
+        assert_eq!(ymd(2024, 2, 29).weekday(), Ok(Weekday::Thursday));
+        assert_eq!(ymd(1900, 3, 1).weekday(), Ok(Weekday::Thursday));
+        assert_eq!(
+            ymd(2023, 2, 29).weekday(),
+            Err(DateError::InvalidCalendarDate(DateField::Day, 29))
+        );
+        assert_eq!(
+            Date {
                 year: Some(2023),
-                month: Some(10),
-                day: Some(5),
-            }),
-            Ok(Date {
-                year: Some(2021),
-                month: Some(11),
-                day: Some(21),
-            }),
-        ];
-        assert_eq!(dates, expected);
+                month: None,
+                day: Some(5)
+            }
+            .weekday(),
+            Err(DateError::IncompleteDate((Some(2023), None, Some(5))))
+        );
     }
 
     #[test]
-    fn cast_date_holder_to_date_1() {
-        let date = DateHolder {
-            holding: vec![
-                Part(vec!['2', '0', '2', '3']),
-                Part(vec!['1', '0']),
-                Part(vec!['0', '5']),
-            ],
+    fn time_check() {
+        let leap_second = "23:59:60".parse::<Time>().unwrap();
+        assert_eq!(leap_second, Time::new(23, 59, 60).unwrap());
+        assert_eq!(leap_second.to_string(), "23:59:60");
+        assert_eq!(
+            "24:00:00".parse::<Time>(),
+            Err(DateError::InvalidTime((24, 0, 0)))
+        );
+        let half_past = "12:30".parse::<Time>().unwrap();
+        assert_eq!(
+            (half_past.hour(), half_past.minute(), half_past.second()),
+            (12, 30, 0)
+        );
+        assert_eq!(half_past.to_string(), "12:30:00");
+        assert_eq!(
+            Time::new(12, 60, 0),
+            Err(DateError::InvalidTime((12, 60, 0)))
+        );
+        assert_eq!(Time::new(0, 0, 61), Err(DateError::InvalidTime((0, 0, 61))));
+        for s in ["12", "12:3", "12:30:00:00", "1a:30", ""] {
+            assert_eq!(
+                s.parse::<Time>(),
+                Err(DateError::InvalidDateFormat(s.to_string()))
+            );
         }
-        .as_date()
-        .unwrap();
-        assert_eq!(date, Date {
+        assert!(Time::new(9, 0, 0).unwrap() < half_past);
+    }
+
+    #[test]
+    fn split_compact_dates_check() {
+        let date = |year| Ok(ymd(year, 10, 5));
+        let compact = ParseOptions {
+            split_compact_dates: true,
+            ..Default::default()
+        };
+        assert_eq!(find_dates_with("20231005", &compact), vec![date(2023)]);
+        assert_eq!(find_dates_with("20231005_report.pdf", &compact), vec![
+            date(2023)
+        ]);
+        assert_eq!(find_dates_with("231005", &compact), vec![date(2023)]);
+        assert_eq!(find_dates_with("991005 backup", &compact), vec![date(1999)]);
+        let as_written = ParseOptions {
+            two_digit_year_pivot: None,
+            ..compact.clone()
+        };
+        assert_eq!(find_dates_with("051005", &as_written), vec![date(5)]);
+        assert_eq!(find_dates_with("2023105 and 123", &compact), vec![]);
+        assert_eq!(
+            find_dates_with("20231005_report.pdf", &ParseOptions::default()),
+            vec![]
+        );
+        assert_eq!(find_dates_with("20231305", &compact), vec![Err(
+            DateError::InvalidCalendarDate(DateField::Month, 13)
+        )]);
+    }
+
+    #[test]
+    fn succ_pred_opt_check() {
+        assert_eq!(ymd(2023, 12, 31).succ_opt(), Some(ymd(2024, 1, 1)));
+        assert_eq!(ymd(2024, 3, 1).pred_opt(), Some(ymd(2024, 2, 29)));
+        assert_eq!(ymd(0, 1, 1).pred_opt(), None);
+        assert_eq!(ymd(0, 1, 1).succ_opt(), Some(ymd(0, 1, 2)));
+        assert_eq!(ymd(65535, 12, 31).succ_opt(), None);
+        assert_eq!(ymd(65535, 12, 31).pred_opt(), Some(ymd(65535, 12, 30)));
+        assert_eq!(ymd(2023, 2, 30).succ_opt(), None);
+        let partial = Date {
+            year: Some(2023),
+            month: Some(10),
+            day: None,
+        };
+        assert_eq!(partial.succ_opt(), None);
+        assert_eq!(partial.pred_opt(), None);
+    }
+
+    #[test]
+    fn lone_years_check() {
+        let lone = ParseOptions {
+            lone_years: true,
+            ..Default::default()
+        };
+        let year = |year| {
+            Ok(Date {
+                year: Some(year),
+                month: None,
+                day: None,
+            })
+        };
+        assert_eq!(find_dates_with("2023", &lone), vec![year(2023)]);
+        assert_eq!(find_dates_with("founded in 1998 by", &lone), vec![year(
+            1998
+        )]);
+        assert_eq!(find_dates_with("in 2023, then", &lone), vec![year(2023)]);
+        assert_eq!(find_dates_with("2023-10", &lone), vec![Ok(Date {
+            year: Some(2023),
+            month: Some(10),
+            day: None
+        })]);
+        assert_eq!(find_dates_with("10-05", &lone), vec![Err(
+            DateError::UndecidedDate((Some(10), Some(5), None))
+        )]);
+        assert_eq!(find_dates_with("123 or 12345", &lone), vec![]);
+        assert_eq!(find_dates("founded in 1998 by"), vec![]);
+    }
+
+    #[test]
+    fn parse_leading_date_check() {
+        let date = Date {
             year: Some(2023),
             month: Some(10),
             day: Some(5),
+        };
+        assert_eq!(
+            parse_leading_date("2023-10-05 rest of text"),
+            Ok((date, " rest of text"))
+        );
+        assert_eq!(
+            parse_leading_date("  5 Oct 2023, then"),
+            Ok((date, ", then"))
+        );
+        assert_eq!(parse_leading_date("2023-10-05"), Ok((date, "")));
+        let (first, rest) = parse_leading_date("2023-10-05;2024-02-29;").unwrap();
+        assert_eq!(first, date);
+        assert_eq!(
+            parse_leading_date(&rest[1..]).map(|(date, rest)| (date.to_string(), rest)),
+            Ok(("2024-02-29".to_string(), ";"))
+        );
+        assert_eq!(
+            parse_leading_date("due 2023-10-05"),
+            Err(DateError::NoDatesFound("due 2023-10-05".to_string()))
+        );
+        assert_eq!(
+            parse_leading_date("2023-13-05 x"),
+            Err(DateError::InvalidCalendarDate(DateField::Month, 13))
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_comparison_check() {
+        let naive = chrono::NaiveDate::from_ymd_opt(2023, 10, 5).unwrap();
+        let date = |year, month, day| Date {
+            year: Some(year),
+            month,
+            day,
+        };
+        assert_eq!(date(2023, Some(10), Some(5)), naive);
+        assert_ne!(date(2023, Some(10), Some(6)), naive);
+        assert!(date(2023, Some(10), Some(4)) < naive);
+        assert!(date(2024, Some(1), Some(1)) > naive);
+        assert_ne!(date(2023, Some(10), None), naive);
+        assert_eq!(date(2023, Some(10), None).partial_cmp(&naive), None);
+        assert_eq!(date(2023, Some(2), Some(30)).partial_cmp(&naive), None);
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_comparison_check() {
+        let other = time::Date::from_calendar_date(2023, time::Month::October, 5).unwrap();
+        let date = |year, month, day| Date {
+            year: Some(year),
+            month,
+            day,
+        };
+        assert_eq!(date(2023, Some(10), Some(5)), other);
+        assert!(date(2023, Some(9), Some(30)) < other);
+        assert!(date(2023, Some(10), Some(6)) > other);
+        assert_ne!(date(2023, None, None), other);
+        assert_eq!(date(2023, None, None).partial_cmp(&other), None);
+    }
+
+    #[test]
+    fn unicode_digits_check() {
+        let parser = DateParser::new(ParseOptions {
+            unicode_digits: true,
+            ..Default::default()
         });
+        let expected = find_dates("2023-10-05");
+        assert_eq!(parser.find_dates("２０２３-１０-０５"), expected);
+        assert_eq!(parser.find_dates("due ٢٠٢٣/١٠/٠٥ sharp"), expected);
+        assert_eq!(parser.find_dates("2023-１0-05"), expected);
+        assert_eq!(parser.find_dates("5 Oct ２０２３"), expected);
+        assert_eq!(find_dates("２０２３-１０-０５"), vec![]);
+        assert_eq!(unicode_digit('９'), Some('9'));
+        assert_eq!(unicode_digit('a'), None);
+        assert_eq!(unicode_digit('\u{66a}'), None);
     }
+
     #[test]
-    fn cast_date_holder_to_date_2() {
-        let date = DateHolder {
-            holding: vec![
-                Part(vec!['1', '2']),
-                Part(vec!['1', '0']),
-                Part(vec!['0', '5']),
-            ],
-        }
-        .as_date();
+    fn skip_invalid_compact_check() {
+        let compact = |skip_invalid_compact| ParseOptions {
+            split_compact_dates: true,
+            skip_invalid_compact,
+            ..Default::default()
+        };
+        assert_eq!(find_dates_with("20231345", &compact(false)), vec![Err(
+            DateError::InvalidCalendarDate(DateField::Month, 13)
+        )]);
         assert_eq!(
-            date,
-            Err(DateError::UndecidedDate((Some(12), Some(10), Some(5))))
+            find_dates_with("batch 20231345 shipped", &compact(false)),
+            vec![Err(DateError::InvalidCalendarDate(DateField::Month, 13))]
+        );
+        assert_eq!(find_dates_with("20231345", &compact(true)), vec![]);
+        assert_eq!(find_dates_with("20230230_x", &compact(true)), vec![]);
+        assert_eq!(
+            find_dates_with("20231345 and 20231005", &compact(true)),
+            vec![Ok(Date {
+                year: Some(2023),
+                month: Some(10),
+                day: Some(5)
+            })]
         );
     }
 
     #[test]
-    fn cmp_dates() {
-        let date1 = Date {
+    fn custom_separators_check() {
+        let with = |separators: &[char]| ParseOptions {
+            separators: separators.to_vec().into(),
+            ..Default::default()
+        };
+        assert_eq!(find_dates_with("2023,10,05", &with(&[','])), vec![Ok(ymd(
+            2023, 10, 5
+        ))]);
+        assert_eq!(find_dates_with("at 2023:10:05", &with(&[':', ' '])), vec![
+            Ok(ymd(2023, 10, 5))
+        ]);
+        assert_eq!(find_dates_with("2023-10-05", &with(&[':'])), vec![]);
+        assert_eq!(find_dates("2023,10,05"), vec![]);
+        assert_eq!(
+            find_dates_with("2023-10-05", &ParseOptions::default()),
+            vec![Ok(ymd(2023, 10, 5))]
+        );
+        let (_, config) = find_dates_resolved("", &with(&[',', '-']));
+        assert_eq!(config.separators, vec![',', '-']);
+    }
+
+    #[test]
+    fn group_dates_by_year_check() {
+        let date = |year, month, day| Date {
+            year: Some(year),
+            month: Some(month),
+            day,
+        };
+        let groups = group_dates_by_year(
+            "2024-02-29 then 2023-10-05 and 2023-01 then 2022-12-31 and 2023-13-01 then 2023-03-01",
+        );
+        assert_eq!(groups.keys().copied().collect::<Vec<_>>(), [
+            2022, 2023, 2024
+        ]);
+        assert_eq!(groups[&2022], [date(2022, 12, Some(31))]);
+        assert_eq!(groups[&2023], [
+            date(2023, 10, Some(5)),
+            date(2023, 1, None),
+            date(2023, 3, Some(1))
+        ]);
+        assert_eq!(groups[&2024], [date(2024, 2, Some(29))]);
+        assert!(group_dates_by_year("no dates here").is_empty());
+    }
+
+    #[test]
+    fn parse_one_check() {
+        assert_eq!(parse_one("2023-10-05"), Ok(ymd(2023, 10, 5)));
+        assert_eq!(parse_one(" 5 Oct 2023\n"), Ok(ymd(2023, 10, 5)));
+        assert_eq!(
+            ["2023-10-05", "25/12/2023"].map(parse_one)[0],
+            Ok(ymd(2023, 10, 5))
+        );
+        assert_eq!(parse_one(""), Err(DateError::NoDatesFound("".to_string())));
+        assert_eq!(
+            parse_one("  "),
+            Err(DateError::NoDatesFound("  ".to_string()))
+        );
+        assert_eq!(
+            parse_one("2023-10-05 junk"),
+            Err(DateError::InvalidDateFormat("2023-10-05 junk".to_string()))
+        );
+        assert_eq!(
+            parse_one("2023-10-05 and 2023-10-06"),
+            Err(DateError::InvalidDateFormat(
+                "2023-10-05 and 2023-10-06".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_one("05/06/2023"),
+            Err(DateError::UndecidedDate((Some(5), Some(6), Some(2023))))
+        );
+        assert_eq!(
+            parse_one("2023-02-30"),
+            Err(DateError::InvalidCalendarDate(DateField::Day, 30))
+        );
+    }
+
+    #[test]
+    fn month_name_after_full_date_check() {
+        assert_eq!(find_dates("release 2023-10-05 may slip"), vec![Ok(ymd(
+            2023, 10, 5
+        ))]);
+        assert_eq!(find_dates("2023-10-05 Oct report"), vec![Ok(ymd(
+            2023, 10, 5
+        ))]);
+        assert_eq!(find_dates("2023-10-05 Oct 6 2023"), vec![
+            Ok(ymd(2023, 10, 5)),
+            Ok(Date {
+                year: Some(2023),
+                month: Some(10),
+                day: Some(6),
+            })
+        ]);
+        assert_eq!(find_dates("5 Oct 2023"), vec![Ok(ymd(2023, 10, 5))]);
+    }
+
+    #[test]
+    fn month_name_before_full_date_check() {
+        for text in [
+            "we may 2023-10-05 ship",
+            "Dec 2023-10-05",
+            "the march 2023-10-05",
+            "sat jan 2023-10-05",
+            "shipped Oct 2023-10-05",
+        ] {
+            assert_eq!(find_dates(text), vec![Ok(ymd(2023, 10, 5))], "{text}");
+        }
+        assert_eq!(find_dates("it may 10/05/2023"), find_dates("10/05/2023"));
+    }
+
+    #[test]
+    fn at_sign_inside_word_check() {
+        assert_eq!(find_dates("deadline@2023-10-05"), vec![Ok(Date {
             year: Some(2023),
             month: Some(10),
             day: Some(5),
-        };
-        let date2 = Date {
-            year: Some(2022),
-            month: Some(12),
-            day: Some(31),
-        };
-        assert!(date1 > date2);
-        let date3 = Date {
+        })]);
+        assert_eq!(find_dates("ping me@10/05/2023"), find_dates("10/05/2023"));
+        assert_eq!(find_dates("ping me @10/05/2023"), find_dates("10/05/2023"));
+        assert_eq!(find_dates("due @2023-10-05"), find_dates("2023-10-05"));
+        assert_eq!(find_dates("at @1696500000."), vec![Ok(Date {
             year: Some(2023),
-            month: None,
-            day: None,
+            month: Some(10),
+            day: Some(5),
+        })]);
+    }
+
+    #[test]
+    fn compact_time_not_split_check() {
+        let compact = ParseOptions {
+            split_compact_dates: true,
+            lone_years: true,
+            ..Default::default()
         };
-        assert!(date3 < date1);
-        let date4 = Date {
+        let expected = vec![Ok(Date {
             year: Some(2023),
             month: Some(10),
-            day: None,
-        };
-        assert!(date3 < date4);
+            day: Some(5),
+        })];
+        assert_eq!(find_dates_with("20231005T143000", &compact), expected);
+        assert_eq!(find_dates_with("20231005T143000Z done", &compact), expected);
+        assert_eq!(find_dates_with("20231005T1430", &compact), expected);
+    }
+
+    #[test]
+    fn ordinal_out_of_range_day_check() {
+        let last_day = Date::from_parts(Some(2023), Some(12), Some(31)).unwrap();
+        assert_eq!(last_day.ordinal(), Some(365));
+        assert_eq!(last_day.days_remaining_in_year(), Some(0));
+        assert_eq!(last_day.with_day(40).ordinal(), None);
+        assert_eq!(last_day.with_day(40).days_remaining_in_year(), None);
+        assert_eq!(last_day.with_day(0).ordinal(), None);
+    }
+
+    #[test]
+    fn display_round_trip_check() {
+        let dates = [
+            Date {
+                year: Some(5),
+                month: Some(10),
+                day: None,
+            },
+            Date {
+                year: Some(12),
+                month: Some(1),
+                day: None,
+            },
+            Date {
+                year: Some(5),
+                month: Some(10),
+                day: Some(6),
+            },
+            Date {
+                year: Some(5),
+                month: None,
+                day: None,
+            },
+        ];
+        for date in dates {
+            assert_eq!(date.to_string().parse::<Date>(), Ok(date), "{date}");
+        }
+        assert_eq!(
+            "05-10".parse::<Date>(),
+            Err(DateError::UndecidedDate((Some(5), Some(10), None)))
+        );
+    }
+
+    #[test]
+    fn parse_comma_named_date_check() {
+        assert_eq!(find_dates("October 5, 2023"), vec![Ok(ymd(2023, 10, 5))]);
+        assert_eq!(parse_one("October 5, 2023"), Ok(ymd(2023, 10, 5)));
+        assert_eq!("October 5, 2023".parse::<Date>(), Ok(ymd(2023, 10, 5)));
+        assert_eq!("OCT 5 2023".parse::<Date>(), Ok(ymd(2023, 10, 5)));
+        assert_eq!(
+            "October 5, 2023 x".parse::<Date>(),
+            Err(DateError::InvalidDateFormat(
+                "October 5, 2023 x".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_exact_two_digit_year_check() {
+        let date = |year| Ok(ymd(year, 10, 5));
+        assert_eq!(parse_exact("05/10/23", "%d/%m/%y"), date(2023));
+        assert_eq!(parse_exact("05/10/99", "%d/%m/%y"), date(1999));
+        assert_eq!(
+            parse_exact("05/10/23", "%d/%m/%y"),
+            find_dates_with("05/10/23", &ParseOptions::day_first())[0]
+        );
+        assert_eq!(parse_exact("05/10/0023", "%d/%m/%Y"), date(23));
     }
 }